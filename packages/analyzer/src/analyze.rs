@@ -1,12 +1,16 @@
+use schemars::JsonSchema;
 use serde::Serialize;
-use std::collections::HashMap;
-use std::path::Path;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 use crate::AnalysisError;
 use crate::config::AnalyzerConfig;
+use crate::definition::{ComponentWrapping, DefinitionInspector, SerializableWrapping};
+use crate::diagnostic::Diagnostic;
 use crate::parser::{ImportBinding, ImportedName, OxcParser, SourceFile, SourceLocation};
-use crate::resolver::ModuleResolver;
-use crate::result::AnalysisMetadata;
+use crate::resolver::{ModuleResolver, ResolvedModule};
+use crate::result::{AnalysisMetadata, GitInfo};
 use crate::service::{Package, ProjectContext};
 
 pub struct Analyzer {
@@ -24,21 +28,38 @@ impl Analyzer {
         &self,
         file_path: &Path,
         context: &AnalysisContext,
-    ) -> Result<Vec<ComponentUsage>, AnalysisError> {
+    ) -> Result<FileAnalysis, AnalysisError> {
+        let parse_started = Instant::now();
         let source_file = SourceFile::new(file_path, context.project_context().root())?;
         let source_text = std::fs::read_to_string(file_path)?;
-        let parsed_file = self.parser.parse(&source_text, &source_file)?;
+        let parsed_file = self
+            .parser
+            .with_create_element_calls(context.config().collect_create_element_calls)
+            .parse(&source_text, &source_file)?;
+        let diagnostics = parsed_file.diagnostics().to_vec();
+        let parse_duration = parse_started.elapsed();
 
         if parsed_file.jsx_elements().is_empty() {
-            return Ok(Vec::new());
+            return Ok(FileAnalysis {
+                diagnostics,
+                parse_duration,
+                ..FileAnalysis::default()
+            });
         }
 
+        let resolve_started = Instant::now();
+        let mut unresolved = 0;
         let usages: Vec<ComponentUsage> = parsed_file
             .jsx_elements()
             .iter()
             .filter_map(|element| {
                 let binding = parsed_file.find_binding_for_element(element);
-                let definition = context.identify_component(element, binding)?;
+                let Some(definition) = context.identify_component(element, binding) else {
+                    if !element.tag_name().is_native() {
+                        unresolved += 1;
+                    }
+                    return None;
+                };
                 let usage_package = context.resolve_package_for_file(element.location());
 
                 Some(ComponentUsage::new(
@@ -50,10 +71,32 @@ impl Analyzer {
             })
             .collect();
 
-        Ok(usages)
+        Ok(FileAnalysis {
+            jsx_elements: parsed_file.jsx_elements().len(),
+            unresolved,
+            usages,
+            diagnostics,
+            parse_duration,
+            resolve_duration: resolve_started.elapsed(),
+        })
     }
 }
 
+/// Result of analyzing a single file
+#[derive(Debug, Default)]
+pub struct FileAnalysis {
+    pub usages: Vec<ComponentUsage>,
+    pub diagnostics: Vec<Diagnostic>,
+    /// Number of JSX elements found in the file
+    pub jsx_elements: usize,
+    /// JSX elements whose component could not be identified
+    pub unresolved: usize,
+    /// Time spent reading and parsing the file
+    pub parse_duration: Duration,
+    /// Time spent identifying the components of the elements
+    pub resolve_duration: Duration,
+}
+
 impl Default for Analyzer {
     fn default() -> Self {
         Self::new()
@@ -64,6 +107,7 @@ pub struct AnalysisContext {
     project_context: ProjectContext,
     module_resolver: ModuleResolver,
     config: AnalyzerConfig,
+    definition_inspector: DefinitionInspector,
 }
 
 impl AnalysisContext {
@@ -76,6 +120,7 @@ impl AnalysisContext {
             project_context,
             module_resolver,
             config,
+            definition_inspector: DefinitionInspector::new(),
         }
     }
 
@@ -89,6 +134,9 @@ impl AnalysisContext {
 
     pub fn metadata(&self) -> AnalysisMetadata {
         AnalysisMetadata::new(self.project_context.root().to_path_buf())
+            .with_package(self.project_context.package_info().clone())
+            .with_git(GitInfo::detect(self.project_context.root()))
+            .with_config(self.config.clone())
     }
 
     pub fn resolve_package_for_file(&self, source_file: &SourceLocation) -> Option<Package> {
@@ -115,75 +163,111 @@ impl AnalysisContext {
         }
 
         let binding = binding?;
+        let tag_members = match element.tag_name() {
+            JSXElementReference::MemberAccess { members, .. } => members.as_slice(),
+            JSXElementReference::Direct(_) => &[],
+        };
 
-        let resolved = self
-            .module_resolver
-            .resolve(binding.source(), element.location().file())
-            .ok()?;
-
-        let export_name = match binding.imported_name() {
-            ImportedName::Named(name) => ExportName::direct(name.clone()),
-            ImportedName::Default => ExportName::direct("default"),
-            ImportedName::Namespace => match element.tag_name() {
-                JSXElementReference::MemberAccess { object: _, members } => {
-                    if members.is_empty() {
-                        return None;
-                    }
-                    if members.len() == 1 {
-                        ExportName::direct(members[0].clone())
-                    } else {
-                        ExportName::member(members[0].clone(), members[1].clone())
-                    }
-                }
-                _ => return None,
-            },
+        let (identity, resolved_path) =
+            self.resolve_identity(binding, element.location().file(), tag_members)?;
+
+        let wraps = match identity.export_name().segments() {
+            // Rolling up usages needs the wrappers
+            [export] if self.config.detect_wrappers || self.config.roll_up_wrapped_usages => self
+                .definition_inspector
+                .inspect(self, &resolved_path, export),
+            _ => None,
         };
 
-        let resolved_path_str = resolved.canonical_path().display().to_string();
+        Some(ComponentDefinition::new(identity, Some(element.location().clone())).with_wraps(wraps))
+    }
+
+    /// Resolve the identity of the component an import binding refers to
+    ///
+    /// Returns the identity along with the canonical path of the module it was resolved to.
+    pub(crate) fn resolve_identity(
+        &self,
+        binding: &ImportBinding,
+        from: &SourceFile,
+        tag_members: &[String],
+    ) -> Option<(ComponentIdentity, PathBuf)> {
+        let resolved = self.module_resolver.resolve(binding.source(), from).ok()?;
+
+        // Full export path: imported name, members taken by aliases, then members in the tag
+        let mut segments = match binding.imported_name() {
+            ImportedName::Named(name) | ImportedName::Lazy(name) => vec![name.clone()],
+            ImportedName::Default => vec!["default".to_string()],
+            ImportedName::Namespace => Vec::new(),
+        };
+        segments.extend(binding.member_path().iter().cloned());
+        segments.extend(tag_members.iter().cloned());
+        if segments.is_empty() {
+            return None;
+        }
+        let export_name = ExportName::path(segments);
+
+        let (source, package) = self.component_source(&resolved)?;
+        let identity = ComponentIdentity::new(source, export_name, package);
+
+        Some((identity, resolved.canonical_path().to_path_buf()))
+    }
+
+    fn component_source(
+        &self,
+        resolved: &ResolvedModule,
+    ) -> Option<(ComponentSource, Option<Package>)> {
+        let canonical_path = resolved.canonical_path();
+        let resolved_path_str = canonical_path.display().to_string();
         let is_external = resolved_path_str.contains("node_modules");
 
-        let (source, package) = if is_external {
+        if is_external {
             let package = resolved.package_info()?.clone();
-            (
+            Some((
                 ComponentSource::External {
                     package: package.clone(),
                 },
                 Some(package),
-            )
+            ))
         } else {
-            let canonical_path = resolved.canonical_path().display().to_string();
-
-            let relative_path = if let Ok(stripped) = resolved
-                .canonical_path()
-                .strip_prefix(self.project_context.root())
-            {
-                stripped.display().to_string()
-            } else {
-                canonical_path.clone()
-            };
+            let relative_path =
+                if let Ok(stripped) = canonical_path.strip_prefix(self.project_context.root()) {
+                    stripped.display().to_string()
+                } else {
+                    resolved_path_str
+                };
 
             let package = resolved.package_info()?.clone();
-            (
+            Some((
                 ComponentSource::Internal {
                     canonical_path: relative_path,
                 },
                 Some(package),
-            )
-        };
-
-        let identity = ComponentIdentity::new(source, export_name, package);
+            ))
+        }
+    }
 
-        Some(ComponentDefinition::new(
-            identity,
-            Some(element.location().clone()),
+    /// Identity of a component declared locally in a module (not necessarily exported)
+    pub(crate) fn local_identity(&self, path: &Path, name: &str) -> Option<ComponentIdentity> {
+        let package = self.module_resolver.resolve_package_for_path(path);
+        let resolved = ResolvedModule::new(path.to_path_buf(), package);
+        let (source, package) = self.component_source(&resolved)?;
+        Some(ComponentIdentity::new(
+            source,
+            ExportName::direct(name),
+            package,
         ))
     }
+
+    pub(crate) fn module_resolver(&self) -> &ModuleResolver {
+        &self.module_resolver
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct ComponentDefinition {
     identity: ComponentIdentity,
     source_location: Option<SourceLocation>,
+    wraps: Option<ComponentWrapping>,
 }
 
 impl ComponentDefinition {
@@ -191,9 +275,20 @@ impl ComponentDefinition {
         Self {
             identity,
             source_location,
+            wraps: None,
         }
     }
 
+    pub fn with_wraps(mut self, wraps: Option<ComponentWrapping>) -> Self {
+        self.wraps = wraps;
+        self
+    }
+
+    /// Wrapper calls (`styled`, `memo`, ...) the component is defined with
+    pub fn wraps(&self) -> Option<&ComponentWrapping> {
+        self.wraps.as_ref()
+    }
+
     pub fn identity(&self) -> &ComponentIdentity {
         &self.identity
     }
@@ -209,6 +304,7 @@ pub struct ComponentUsage {
     occurrence: JSXElementOccurrence,
     binding: Option<ImportBinding>,
     simplified_props: Vec<SimplifiedProp>,
+    nested_props: Vec<SimplifiedProp>,
     usage_package: Option<UsagePackageSchema>,
 }
 
@@ -220,12 +316,15 @@ impl ComponentUsage {
         usage_package: Option<Package>,
     ) -> Self {
         let mut simplified_props: Vec<SimplifiedProp> = Vec::new();
+        let mut nested_props: Vec<SimplifiedProp> = Vec::new();
 
         // Process regular props and spread props
         for attr in occurrence.attributes() {
             match attr {
                 JSXAttribute::Regular(prop) => {
                     simplified_props.push(prop.to_simplified());
+                    // Properties of object literals as `style.margin`
+                    nested_props.extend(prop.to_simplified_properties());
                 }
                 JSXAttribute::Spread(spread) => {
                     // Expand spread (individual props if analyzable, spread itself otherwise)
@@ -264,6 +363,7 @@ impl ComponentUsage {
             occurrence,
             binding,
             simplified_props,
+            nested_props,
             usage_package: usage_package_schema,
         }
     }
@@ -284,6 +384,13 @@ impl ComponentUsage {
         &self.simplified_props
     }
 
+    /// Properties of object literal props keyed as `style.margin`
+    ///
+    /// They are only counted in the prop patterns, not listed with the usage's props.
+    pub fn nested_props(&self) -> &[SimplifiedProp] {
+        &self.nested_props
+    }
+
     pub fn to_serializable(&self) -> SerializableComponentUsage {
         SerializableComponentUsage {
             file_path: self.occurrence.location().file().display_path(),
@@ -303,8 +410,17 @@ impl ComponentUsage {
                 .source_location()
                 .map(|loc| loc.file().display_path()),
             usage_package_schema: self.usage_package.clone(),
+            code_split: self.is_code_split(),
+            context: self.occurrence.context().clone(),
         }
     }
+
+    /// Whether the component is loaded through `lazy()` / `dynamic()`
+    pub fn is_code_split(&self) -> bool {
+        self.binding
+            .as_ref()
+            .is_some_and(|b| b.imported_name().is_lazy())
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -314,13 +430,28 @@ pub struct ComponentUsageAggregate {
     display_name: String,
     usages: Vec<ComponentUsage>,
     statistics: UsageStatistics,
+    wraps: Option<ComponentWrapping>,
+    /// IDs of wrapping components whose usages are counted in `statistics`
+    rolled_up: Vec<String>,
+    id_options: IdOptions,
 }
 
 impl ComponentUsageAggregate {
     pub fn new(identity: ComponentIdentity, usages: Vec<ComponentUsage>) -> Self {
-        let id = identity.generate_id();
+        Self::new_with_id_options(identity, usages, IdOptions::default())
+    }
+
+    pub fn new_with_id_options(
+        identity: ComponentIdentity,
+        usages: Vec<ComponentUsage>,
+        id_options: IdOptions,
+    ) -> Self {
+        let id = identity.generate_id_with(id_options);
         let display_name = compute_display_name(&identity, &usages);
         let statistics = UsageStatistics::compute(&usages);
+        let wraps = usages
+            .iter()
+            .find_map(|usage| usage.definition().wraps().cloned());
 
         Self {
             id,
@@ -328,6 +459,9 @@ impl ComponentUsageAggregate {
             display_name,
             usages,
             statistics,
+            wraps,
+            rolled_up: Vec::new(),
+            id_options,
         }
     }
 
@@ -351,6 +485,29 @@ impl ComponentUsageAggregate {
         &self.statistics
     }
 
+    pub fn wraps(&self) -> Option<&ComponentWrapping> {
+        self.wraps.as_ref()
+    }
+
+    pub fn rolled_up(&self) -> &[String] {
+        &self.rolled_up
+    }
+
+    /// Use statistics that include the usages of the components wrapping this one
+    fn set_rolled_up(&mut self, statistics: UsageStatistics, wrapper_ids: Vec<String>) {
+        self.statistics = statistics;
+        self.rolled_up = wrapper_ids;
+    }
+
+    /// Keep only the usages matching `f`, recomputing the statistics from them
+    ///
+    /// Usages rolled up from wrapping components are dropped from the statistics.
+    pub fn retain_usages(&mut self, f: impl FnMut(&ComponentUsage) -> bool) {
+        self.usages.retain(f);
+        self.statistics = UsageStatistics::compute(&self.usages);
+        self.rolled_up.clear();
+    }
+
     pub fn to_serializable(&self) -> SerializableComponentGroup {
         SerializableComponentGroup {
             id: self.id.clone(),
@@ -363,6 +520,11 @@ impl ComponentUsageAggregate {
                 .iter()
                 .map(|p| p.to_serializable())
                 .collect(),
+            wraps: self
+                .wraps
+                .as_ref()
+                .map(|w| w.to_serializable(self.id_options)),
+            rolled_up: self.rolled_up.clone(),
         }
     }
 
@@ -387,7 +549,10 @@ impl ComponentUsageAggregate {
     }
 }
 
-fn compute_display_name(identity: &ComponentIdentity, usages: &[ComponentUsage]) -> String {
+pub(crate) fn compute_display_name(
+    identity: &ComponentIdentity,
+    usages: &[ComponentUsage],
+) -> String {
     use crate::analyze::ComponentSource;
 
     if matches!(identity.source(), ComponentSource::Native) {
@@ -397,18 +562,20 @@ fn compute_display_name(identity: &ComponentIdentity, usages: &[ComponentUsage])
         return String::new();
     }
 
-    let export_name = identity.display_name();
-
-    if export_name == "default" {
+    // The default export has no name of its own, so use the local name at the call site
+    if identity.export_name().is_default() {
         if let Some(first_usage) = usages.first() {
             return first_usage.occurrence().tag_name().display_name();
         }
     }
 
-    export_name
+    identity.display_name()
 }
 
-pub fn group_by_identity(usages: Vec<ComponentUsage>) -> Vec<ComponentUsageAggregate> {
+pub fn group_by_identity(
+    usages: Vec<ComponentUsage>,
+    id_options: IdOptions,
+) -> Vec<ComponentUsageAggregate> {
     let mut groups: HashMap<ComponentIdentity, Vec<ComponentUsage>> = HashMap::new();
 
     for usage in usages {
@@ -418,10 +585,71 @@ pub fn group_by_identity(usages: Vec<ComponentUsage>) -> Vec<ComponentUsageAggre
 
     groups
         .into_iter()
-        .map(|(identity, usages)| ComponentUsageAggregate::new(identity, usages))
+        .map(|(identity, usages)| {
+            ComponentUsageAggregate::new_with_id_options(identity, usages, id_options)
+        })
         .collect()
 }
 
+/// Add usages of wrapping components (`styled(Button)`, `memo(Button)`, ...) to the
+/// statistics of the component they wrap
+///
+/// Wrappers of wrappers count as well, so `styled(memo(Button))` rolls up into `Button`.
+pub fn roll_up_wrapped(aggregates: &mut [ComponentUsageAggregate]) {
+    let index_by_identity: HashMap<&ComponentIdentity, usize> = aggregates
+        .iter()
+        .enumerate()
+        .map(|(index, aggregate)| (aggregate.identity(), index))
+        .collect();
+    let mut direct_wrappers: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (index, aggregate) in aggregates.iter().enumerate() {
+        let inner = aggregate
+            .wraps()
+            .and_then(|w| w.inner())
+            .and_then(|inner| index_by_identity.get(inner));
+        if let Some(&inner) = inner {
+            direct_wrappers.entry(inner).or_default().push(index);
+        }
+    }
+
+    let rolled_up: Vec<(usize, UsageStatistics, Vec<String>)> = direct_wrappers
+        .keys()
+        .map(|&inner| {
+            let wrappers = transitive_wrappers(inner, &direct_wrappers);
+            let usages = std::iter::once(inner)
+                .chain(wrappers.iter().copied())
+                .flat_map(|index| aggregates[index].usages());
+            let wrapper_ids = wrappers
+                .iter()
+                .map(|&index| aggregates[index].id().to_string())
+                .collect();
+            (inner, UsageStatistics::compute(usages), wrapper_ids)
+        })
+        .collect();
+
+    for (index, statistics, wrapper_ids) in rolled_up {
+        aggregates[index].set_rolled_up(statistics, wrapper_ids);
+    }
+}
+
+/// Indices of the aggregates wrapping `inner` directly or through other wrappers
+fn transitive_wrappers(inner: usize, direct_wrappers: &HashMap<usize, Vec<usize>>) -> Vec<usize> {
+    // Wrapping cycles are not valid code but must not loop forever
+    let mut seen = HashSet::from([inner]);
+    let mut pending = vec![inner];
+    let mut wrappers = Vec::new();
+    while let Some(index) = pending.pop() {
+        for &wrapper in direct_wrappers.get(&index).into_iter().flatten() {
+            if seen.insert(wrapper) {
+                wrappers.push(wrapper);
+                pending.push(wrapper);
+            }
+        }
+    }
+    wrappers.sort_unstable();
+    wrappers
+}
+
 #[derive(Debug, Clone)]
 pub struct SerializableComponentUsage {
     pub file_path: String,
@@ -431,9 +659,11 @@ pub struct SerializableComponentUsage {
     pub import_specifier: Option<String>,
     pub resolved_path: Option<String>,
     pub usage_package_schema: Option<UsagePackageSchema>,
+    pub code_split: bool,
+    pub context: RenderContext,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct SerializableComponentGroup {
     pub id: String,
     pub name: String,
@@ -441,6 +671,10 @@ pub struct SerializableComponentGroup {
     pub identity: ComponentIdentity,
     pub instances: Vec<SerializableComponentUsage>,
     pub props_usages: Vec<SerializablePropUsage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wraps: Option<SerializableWrapping>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub rolled_up: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -450,9 +684,10 @@ pub struct UsageStatistics {
 }
 
 impl UsageStatistics {
-    pub fn compute(usages: &[ComponentUsage]) -> Self {
+    pub fn compute<'u>(usages: impl IntoIterator<Item = &'u ComponentUsage>) -> Self {
+        let usages: Vec<&ComponentUsage> = usages.into_iter().collect();
         let total_count = usages.len();
-        let prop_patterns = compute_prop_patterns(usages);
+        let prop_patterns = compute_prop_patterns(&usages);
 
         Self {
             total_count,
@@ -519,6 +754,8 @@ pub struct ValueDistribution {
     value_pattern: String,
     value: Option<String>,
     raw: String,
+    shape: Option<String>,
+    element_count: Option<usize>,
     count: usize,
 }
 
@@ -528,10 +765,22 @@ impl ValueDistribution {
             value_pattern,
             value,
             raw,
+            shape: None,
+            element_count: None,
             count,
         }
     }
 
+    pub fn with_shape(mut self, shape: Option<String>) -> Self {
+        self.shape = shape;
+        self
+    }
+
+    pub fn with_element_count(mut self, element_count: Option<usize>) -> Self {
+        self.element_count = element_count;
+        self
+    }
+
     pub fn value_pattern(&self) -> &str {
         &self.value_pattern
     }
@@ -544,6 +793,14 @@ impl ValueDistribution {
         &self.raw
     }
 
+    pub fn shape(&self) -> Option<&str> {
+        self.shape.as_deref()
+    }
+
+    pub fn element_count(&self) -> Option<usize> {
+        self.element_count
+    }
+
     pub fn count(&self) -> usize {
         self.count
     }
@@ -553,6 +810,8 @@ impl ValueDistribution {
             value: self.value.clone(),
             raw: self.raw.clone(),
             prop_type: self.value_pattern.clone(),
+            shape: self.shape.clone(),
+            element_count: self.element_count.map(|n| n as u32),
             count: self.count as u32,
         }
     }
@@ -632,31 +891,45 @@ fn create_children_prop(children: &[ChildNode]) -> Option<SimplifiedProp> {
         value_pattern,
         value,
         raw,
+        shape: None,
+        element_count: None,
+        branch_values: Vec::new(),
+        type_annotation: None,
     })
 }
 
 type PropValueKey = (String, Option<String>, String);
-type PropPatternMap = HashMap<String, HashMap<PropValueKey, usize>>;
+type PropPatternMap =
+    HashMap<String, HashMap<PropValueKey, (Option<String>, Option<usize>, usize)>>;
 
-fn compute_prop_patterns(usages: &[ComponentUsage]) -> Vec<PropPattern> {
+fn compute_prop_patterns(usages: &[&ComponentUsage]) -> Vec<PropPattern> {
     // Count with (pattern, value, raw) as key
     let mut prop_map: PropPatternMap = HashMap::new();
 
     for usage in usages {
-        for prop in usage.simplified_props() {
+        for prop in usage.simplified_props().iter().chain(usage.nested_props()) {
             let key = prop.key().to_string();
-            let value_type_triple = (
-                prop.value_pattern().to_string(),
-                prop.value().map(|s| s.to_string()),
-                prop.raw().to_string(),
-            );
+            // Each literal branch of a conditional counts as a value of its own
+            let values: Vec<Option<String>> = if prop.branch_values().is_empty() {
+                vec![prop.value().map(|s| s.to_string())]
+            } else {
+                prop.branch_values().iter().cloned().map(Some).collect()
+            };
 
-            prop_map
-                .entry(key)
-                .or_default()
-                .entry(value_type_triple)
-                .and_modify(|count| *count += 1)
-                .or_insert(1);
+            for value in values {
+                let value_type_triple = (
+                    prop.value_pattern().to_string(),
+                    value,
+                    prop.raw().to_string(),
+                );
+
+                prop_map
+                    .entry(key.clone())
+                    .or_default()
+                    .entry(value_type_triple)
+                    .and_modify(|(_, _, count)| *count += 1)
+                    .or_insert((prop.shape().map(str::to_string), prop.element_count(), 1));
+            }
         }
     }
 
@@ -665,8 +938,10 @@ fn compute_prop_patterns(usages: &[ComponentUsage]) -> Vec<PropPattern> {
         .map(|(key, value_counts)| {
             let distribution: Vec<ValueDistribution> = value_counts
                 .into_iter()
-                .map(|((pattern, value, raw), count)| {
+                .map(|((pattern, value, raw), (shape, element_count, count))| {
                     ValueDistribution::new(pattern, value, raw, count)
+                        .with_shape(shape)
+                        .with_element_count(element_count)
                 })
                 .collect();
 
@@ -675,18 +950,22 @@ fn compute_prop_patterns(usages: &[ComponentUsage]) -> Vec<PropPattern> {
         .collect()
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct SerializablePropUsage {
     pub key: String,
     pub distribution: Vec<SerializablePropDistribution>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct SerializablePropDistribution {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub value: Option<String>,
     pub raw: String,
     pub prop_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shape: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub element_count: Option<u32>,
     pub count: u32,
 }
 use serde::Serializer;
@@ -780,6 +1059,22 @@ impl ChildNode {
     }
 }
 
+/// Where an element is rendered, relative to the code around it
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct RenderContext {
+    /// Inside a `.map()` callback (or `v-for` in Vue)
+    pub in_loop: bool,
+    /// Rendered behind `&&`, `||`, `??` or a ternary (or `v-if` in Vue)
+    pub conditional: bool,
+    /// Inside a function passed as a prop or as children (or a scoped slot in Vue)
+    pub in_render_prop: bool,
+    /// Passed as the value of a prop of another element, e.g. `icon={<Icon />}`
+    pub in_prop: bool,
+    /// Name of the component function the element is rendered in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub component: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct JSXElementOccurrence {
     location: SourceLocation,
@@ -787,6 +1082,7 @@ pub struct JSXElementOccurrence {
     attributes: Vec<JSXAttribute>,
     raw_text: String,
     children: Vec<ChildNode>,
+    context: RenderContext,
 }
 
 impl JSXElementOccurrence {
@@ -803,9 +1099,15 @@ impl JSXElementOccurrence {
             attributes,
             raw_text,
             children,
+            context: RenderContext::default(),
         }
     }
 
+    pub fn with_context(mut self, context: RenderContext) -> Self {
+        self.context = context;
+        self
+    }
+
     pub fn location(&self) -> &SourceLocation {
         &self.location
     }
@@ -825,6 +1127,14 @@ impl JSXElementOccurrence {
     pub fn children(&self) -> &[ChildNode] {
         &self.children
     }
+
+    pub fn context(&self) -> &RenderContext {
+        &self.context
+    }
+
+    pub(crate) fn context_mut(&mut self) -> &mut RenderContext {
+        &mut self.context
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -834,29 +1144,34 @@ pub enum ComponentSource {
     Native,
 }
 
+/// Path of an export, e.g. `["Menu", "Item", "Icon"]` for `<Menu.Item.Icon>`
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub enum ExportName {
-    Direct(String),
-    Member { object: String, property: String },
+pub struct ExportName {
+    segments: Vec<String>,
 }
 
 impl ExportName {
     pub fn direct(name: impl Into<String>) -> Self {
-        Self::Direct(name.into())
+        Self {
+            segments: vec![name.into()],
+        }
     }
 
-    pub fn member(object: impl Into<String>, property: impl Into<String>) -> Self {
-        Self::Member {
-            object: object.into(),
-            property: property.into(),
-        }
+    pub fn path(segments: Vec<String>) -> Self {
+        Self { segments }
+    }
+
+    pub fn segments(&self) -> &[String] {
+        &self.segments
+    }
+
+    /// Whether the path starts from the default export
+    pub fn is_default(&self) -> bool {
+        self.segments.first().is_some_and(|s| s == "default")
     }
 
     pub fn display_name(&self) -> String {
-        match self {
-            Self::Direct(name) => name.clone(),
-            Self::Member { object, property } => format!("{object}.{property}"),
-        }
+        self.segments.join(".")
     }
 }
 
@@ -918,7 +1233,7 @@ impl ComponentIdentity {
     pub fn native(tag_name: impl Into<String>) -> Self {
         Self {
             source: ComponentSource::Native,
-            export_name: ExportName::Direct(tag_name.into()),
+            export_name: ExportName::direct(tag_name),
             package: None,
         }
     }
@@ -939,17 +1254,66 @@ impl ComponentIdentity {
         self.export_name.display_name()
     }
 
-    /// Generate a deterministic hash ID from Identity
+    /// Canonical string form of the identity, used as the input of [`Self::generate_id`]
+    ///
+    /// - `native:<tag>`
+    /// - `internal:<package>[@<version>]:<path>:<export path>`
+    /// - `external:<package>[@<version>]:<export path>`
+    ///
+    /// Paths always use `/` as separator so keys match across platforms.
+    pub fn canonical_key(&self, options: IdOptions) -> String {
+        let package_key = |package: Option<&Package>| match package {
+            Some(package) if options.include_version => {
+                format!("{}@{}", package.name(), package.version())
+            }
+            Some(package) => package.name().to_string(),
+            None => String::new(),
+        };
+        let export = self.export_name.display_name();
+
+        match &self.source {
+            ComponentSource::Native => format!("native:{export}"),
+            ComponentSource::Internal { canonical_path } => format!(
+                "internal:{}:{}:{export}",
+                package_key(self.package.as_ref()),
+                canonical_path.replace('\\', "/")
+            ),
+            ComponentSource::External { package } => {
+                format!("external:{}:{export}", package_key(Some(package)))
+            }
+        }
+    }
+
+    /// Generate a stable ID with the default [`IdOptions`]
     pub fn generate_id(&self) -> String {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
+        self.generate_id_with(IdOptions::default())
+    }
 
-        let mut hasher = DefaultHasher::new();
-        self.hash(&mut hasher);
-        format!("{:016x}", hasher.finish())
+    /// Generate a stable ID from the canonical key
+    ///
+    /// The ID is the 64-bit FNV-1a hash of the UTF-8 bytes of [`Self::canonical_key`],
+    /// formatted as 16 lowercase hex digits. It does not depend on the Rust version or platform.
+    pub fn generate_id_with(&self, options: IdOptions) -> String {
+        format!("{:016x}", fnv1a_64(self.canonical_key(options).as_bytes()))
     }
 }
 
+/// Options for component ID generation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct IdOptions {
+    /// Include the package version in the key, so a version bump changes the ID
+    pub include_version: bool,
+}
+
+fn fnv1a_64(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    bytes.iter().fold(OFFSET_BASIS, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    })
+}
+
 impl Serialize for ComponentIdentity {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -1018,6 +1382,26 @@ impl PropAssignment {
         &self.value
     }
 
+    /// Properties of an object literal value, keyed as `prop.property`
+    pub fn to_simplified_properties(&self) -> Vec<SimplifiedProp> {
+        let PropValue::Expression {
+            kind: ExpressionKind::ObjectLiteral { properties },
+            ..
+        } = &self.value
+        else {
+            return Vec::new();
+        };
+
+        let name = self.name.as_string();
+        properties
+            .iter()
+            .map(|p| SimplifiedProp {
+                key: format!("{name}.{}", p.key()),
+                ..p.to_simplified()
+            })
+            .collect()
+    }
+
     pub fn to_simplified(&self) -> SimplifiedProp {
         let key = self.name.as_string();
         let (value_pattern, value, raw) = self.value.to_pattern_value_and_raw();
@@ -1027,6 +1411,10 @@ impl PropAssignment {
             value_pattern,
             value,
             raw,
+            shape: self.value.shape().map(str::to_string),
+            element_count: self.value.element_count(),
+            branch_values: self.value.branch_values().to_vec(),
+            type_annotation: self.value.type_annotation().map(str::to_string),
         }
     }
 }
@@ -1051,7 +1439,14 @@ impl PropName {
 pub enum PropValue {
     StringLiteral(String),
     BooleanImplicit,
-    Expression { raw: String, kind: ExpressionKind },
+    Expression {
+        raw: String,
+        kind: ExpressionKind,
+        /// Placeholder grouping similar expressions when it differs from `raw`
+        shape: Option<String>,
+        /// TypeScript wrapper the value was written with, e.g. `as const`
+        type_annotation: Option<String>,
+    },
     JSXElement(String),
     JSXFragment,
     Mixed(String),
@@ -1066,21 +1461,30 @@ impl PropValue {
                 Some("true".to_string()),
                 "true".to_string(),
             ),
-            PropValue::Expression { raw, kind } => {
+            PropValue::Expression { raw, kind, .. } => {
                 let pattern = match kind {
                     ExpressionKind::Literal => "literal",
                     ExpressionKind::Identifier => "identifier",
                     ExpressionKind::MemberExpression => "member",
                     ExpressionKind::CallExpression => "call",
                     ExpressionKind::ArrowFunction => "arrow",
-                    ExpressionKind::ConditionalExpression => "conditional",
+                    ExpressionKind::ConditionalExpression { .. } => "conditional",
+                    ExpressionKind::TemplateLiteral { .. } => "template",
+                    ExpressionKind::ObjectLiteral { .. } => "object",
+                    ExpressionKind::ArrayLiteral { .. } => "array",
+                    ExpressionKind::LogicalExpression => "logical",
+                    ExpressionKind::UnaryExpression => "unary",
+                    ExpressionKind::BinaryExpression => "binary",
                     ExpressionKind::Complex => "expression",
                 };
 
-                let value = if matches!(kind, ExpressionKind::Literal) {
-                    Some(raw.clone())
-                } else {
-                    None
+                let value = match kind {
+                    ExpressionKind::Literal => Some(raw.clone()),
+                    // Without substitutions the template is a plain string
+                    ExpressionKind::TemplateLiteral { quasis } if quasis.len() == 1 => {
+                        Some(quasis[0].clone())
+                    }
+                    _ => None,
                 };
 
                 (pattern.to_string(), value, raw.clone())
@@ -1090,17 +1494,64 @@ impl PropValue {
             PropValue::Mixed(raw) => ("mixed".to_string(), None, raw.clone()),
         }
     }
+
+    pub fn shape(&self) -> Option<&str> {
+        match self {
+            PropValue::Expression { shape, .. } => shape.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn element_count(&self) -> Option<usize> {
+        match self {
+            PropValue::Expression {
+                kind: ExpressionKind::ArrayLiteral { element_kinds },
+                ..
+            } => Some(element_kinds.len()),
+            _ => None,
+        }
+    }
+
+    pub fn type_annotation(&self) -> Option<&str> {
+        match self {
+            PropValue::Expression {
+                type_annotation, ..
+            } => type_annotation.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Literal values a conditional expression can take
+    pub fn branch_values(&self) -> &[String] {
+        match self {
+            PropValue::Expression {
+                kind: ExpressionKind::ConditionalExpression { values },
+                ..
+            } => values,
+            _ => &[],
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub enum ExpressionKind {
-    Literal,               // {123}, {true}, {null}
-    Identifier,            // {value}
-    MemberExpression,      // {state.isOpen}, {obj.prop}
-    CallExpression,        // {getValue()}, {fn(arg)}
-    ArrowFunction,         // {() => {}}, {(x) => x + 1}
-    ConditionalExpression, // {condition ? a : b}
-    Complex,               // other
+    Literal,          // {123}, {true}, {null}
+    Identifier,       // {value}
+    MemberExpression, // {state.isOpen}, {obj.prop}
+    CallExpression,   // {getValue()}, {fn(arg)}
+    ArrowFunction,    // {() => {}}, {(x) => x + 1}
+    // {condition ? a : b}, with the literal values of its branches
+    ConditionalExpression { values: Vec<String> },
+    // {`btn ${size}`}, with the static parts (one more than the substitutions)
+    TemplateLiteral { quasis: Vec<String> },
+    // {{ margin: 0 }}, with its statically known properties
+    ObjectLiteral { properties: Vec<ResolvedProp> },
+    // {["a", "b"]}, with the pattern of each element
+    ArrayLiteral { element_kinds: Vec<String> },
+    LogicalExpression, // {a && b}, {a ?? b}
+    UnaryExpression,   // {!enabled}, {typeof x}
+    BinaryExpression,  // {count > 0}, {a + b}
+    Complex,           // other
 }
 
 #[derive(Debug, Clone)]
@@ -1145,6 +1596,10 @@ impl SpreadAttribute {
                 value_pattern: "spread".to_string(),
                 value: None,
                 raw: self.source.clone(),
+                shape: None,
+                element_count: None,
+                branch_values: Vec::new(),
+                type_annotation: None,
             }]
         }
     }
@@ -1156,6 +1611,7 @@ pub struct ResolvedProp {
     value_pattern: String,
     value: Option<String>,
     raw: String,
+    type_annotation: Option<String>,
 }
 
 impl ResolvedProp {
@@ -1165,9 +1621,15 @@ impl ResolvedProp {
             value_pattern,
             value,
             raw,
+            type_annotation: None,
         }
     }
 
+    pub fn with_type_annotation(mut self, type_annotation: Option<String>) -> Self {
+        self.type_annotation = type_annotation;
+        self
+    }
+
     pub fn key(&self) -> &str {
         &self.key
     }
@@ -1184,12 +1646,20 @@ impl ResolvedProp {
         &self.raw
     }
 
+    pub fn type_annotation(&self) -> Option<&str> {
+        self.type_annotation.as_deref()
+    }
+
     pub fn to_simplified(&self) -> SimplifiedProp {
         SimplifiedProp {
             key: self.key.clone(),
             value_pattern: self.value_pattern.clone(),
             value: self.value.clone(),
             raw: self.raw.clone(),
+            shape: None,
+            element_count: None,
+            branch_values: Vec::new(),
+            type_annotation: self.type_annotation.clone(),
         }
     }
 }
@@ -1200,6 +1670,10 @@ pub struct SimplifiedProp {
     value_pattern: String,
     value: Option<String>,
     raw: String,
+    shape: Option<String>,
+    element_count: Option<usize>,
+    branch_values: Vec<String>,
+    type_annotation: Option<String>,
 }
 
 impl SimplifiedProp {
@@ -1219,23 +1693,53 @@ impl SimplifiedProp {
         &self.raw
     }
 
+    pub fn shape(&self) -> Option<&str> {
+        self.shape.as_deref()
+    }
+
+    /// Number of elements when the value is an array literal
+    pub fn element_count(&self) -> Option<usize> {
+        self.element_count
+    }
+
+    /// Literal values of the branches when the value is a conditional
+    pub fn branch_values(&self) -> &[String] {
+        &self.branch_values
+    }
+
+    pub fn type_annotation(&self) -> Option<&str> {
+        self.type_annotation.as_deref()
+    }
+
     pub fn to_serializable(&self) -> SerializableProp {
         SerializableProp {
             key: self.key.clone(),
             value: self.value.clone(),
             raw: self.raw.clone(),
             prop_type: self.value_pattern.clone(),
+            shape: self.shape.clone(),
+            element_count: self.element_count.map(|n| n as u32),
+            branch_values: self.branch_values.clone(),
+            type_annotation: self.type_annotation.clone(),
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct SerializableProp {
     pub key: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub value: Option<String>,
     pub raw: String,
     pub prop_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shape: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub element_count: Option<u32>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub branch_values: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub type_annotation: Option<String>,
 }
 
 impl Serialize for SerializableComponentUsage {
@@ -1246,9 +1750,9 @@ impl Serialize for SerializableComponentUsage {
         use serde::ser::SerializeMap;
 
         let field_count = if self.usage_package_schema.is_some() {
-            7
+            9
         } else {
-            6
+            8
         };
 
         let mut map = serializer.serialize_map(Some(field_count))?;
@@ -1259,6 +1763,8 @@ impl Serialize for SerializableComponentUsage {
         map.serialize_entry("span", &self.span)?;
         map.serialize_entry("import_specifier", &self.import_specifier)?;
         map.serialize_entry("resolved_path", &self.resolved_path)?;
+        map.serialize_entry("code_split", &self.code_split)?;
+        map.serialize_entry("context", &self.context)?;
 
         if let Some(ref schema) = self.usage_package_schema {
             map.serialize_entry("package", schema)?;
@@ -1296,6 +1802,61 @@ mod tests {
         assert_ne!(span_identity.generate_id(), button_identity.generate_id());
     }
 
+    #[test]
+    fn test_nested_export_names_are_distinct() {
+        let item = ExportName::path(vec!["Menu".to_string(), "Item".to_string()]);
+        let icon = ExportName::path(vec![
+            "Menu".to_string(),
+            "Item".to_string(),
+            "Icon".to_string(),
+        ]);
+
+        assert_eq!(item.display_name(), "Menu.Item");
+        assert_eq!(icon.display_name(), "Menu.Item.Icon");
+        assert_ne!(item, icon);
+        assert!(!icon.is_default());
+        assert!(ExportName::direct("default").is_default());
+    }
+
+    #[test]
+    fn test_generate_id_is_stable() {
+        let div_identity = ComponentIdentity::native("div");
+        assert_eq!(
+            div_identity.canonical_key(IdOptions::default()),
+            "native:div"
+        );
+        // FNV-1a 64 of "native:div"; must never change
+        assert_eq!(div_identity.generate_id(), "30687b2d21aec41b");
+
+        let package = Package::new("@acme/ui".to_string(), "1.2.3".to_string());
+        let button = ComponentIdentity::new(
+            ComponentSource::Internal {
+                canonical_path: "src\\ui\\Button.tsx".to_string(),
+            },
+            ExportName::direct("Button"),
+            Some(package.clone()),
+        );
+        assert_eq!(
+            button.canonical_key(IdOptions::default()),
+            "internal:@acme/ui:src/ui/Button.tsx:Button"
+        );
+
+        let bumped = ComponentIdentity::new(
+            button.source().clone(),
+            button.export_name().clone(),
+            Some(Package::new("@acme/ui".to_string(), "2.0.0".to_string())),
+        );
+        assert_eq!(button.generate_id(), bumped.generate_id());
+
+        let with_version = IdOptions {
+            include_version: true,
+        };
+        assert_ne!(
+            button.generate_id_with(with_version),
+            bumped.generate_id_with(with_version)
+        );
+    }
+
     #[test]
     fn test_native_elements_are_grouped_separately() {
         let div_identity = ComponentIdentity::native("div");
@@ -1309,4 +1870,197 @@ mod tests {
         assert_eq!(map.get(&div_identity), Some(&"div_value"));
         assert_eq!(map.get(&span_identity), Some(&"span_value"));
     }
+
+    #[test]
+    fn test_conditional_branches_count_as_values() {
+        let project = crate::testing::TestProject::new(&[(
+            "src/App.tsx",
+            r#"export const App = ({ big }) => (
+  <>
+    <input size={big ? "lg" : "sm"} />
+    <input size="lg" />
+  </>
+);"#,
+        )]);
+        let report = crate::AnalysisService::default()
+            .run(project.root())
+            .unwrap();
+        let input = report
+            .components()
+            .iter()
+            .find(|c| c.display_name() == "input")
+            .unwrap();
+
+        let size = &input.statistics().prop_patterns()[0];
+        let mut values: Vec<_> = size
+            .distribution()
+            .iter()
+            .map(|d| (d.value().unwrap(), d.value_pattern(), d.count()))
+            .collect();
+        values.sort();
+
+        assert_eq!(
+            values,
+            [
+                ("lg", "conditional", 1),
+                ("lg", "string", 1),
+                ("sm", "conditional", 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_object_properties_are_only_counted_in_prop_patterns() {
+        let project = crate::testing::TestProject::new(&[(
+            "src/App.tsx",
+            r#"export const App = () => (
+  <>
+    <div style={{ margin: 0, color: "red" }} data-items={["a", "b", "c"]} />
+    <div style={{ margin: 0 }} />
+  </>
+);"#,
+        )]);
+        let report = crate::AnalysisService::default()
+            .run(project.root())
+            .unwrap();
+        let div = report
+            .components()
+            .iter()
+            .find(|c| c.display_name() == "div")
+            .unwrap();
+
+        for usage in div.usages() {
+            assert!(
+                usage
+                    .simplified_props()
+                    .iter()
+                    .all(|p| !p.key().contains('.'))
+            );
+        }
+        let items = div
+            .usages()
+            .iter()
+            .flat_map(|u| u.simplified_props())
+            .find(|p| p.key() == "data-items")
+            .unwrap();
+        assert_eq!(items.element_count(), Some(3));
+
+        let mut keys: Vec<_> = div
+            .statistics()
+            .prop_patterns()
+            .iter()
+            .map(|p| {
+                let count: usize = p.distribution().iter().map(|d| d.count()).sum();
+                (p.key(), count)
+            })
+            .collect();
+        keys.sort();
+        assert_eq!(
+            keys,
+            [
+                ("data-items", 1),
+                ("style", 2),
+                ("style.color", 1),
+                ("style.margin", 2)
+            ]
+        );
+        let items = div
+            .statistics()
+            .prop_patterns()
+            .iter()
+            .find(|p| p.key() == "data-items")
+            .unwrap();
+        assert_eq!(items.distribution()[0].element_count(), Some(3));
+    }
+
+    #[test]
+    fn test_roll_up_wrapped_usages() {
+        let project = crate::testing::TestProject::new(&[
+            (
+                "src/Button.tsx",
+                "export const Button = (props) => <button {...props} />;",
+            ),
+            (
+                "src/MemoButton.tsx",
+                r#"import { memo } from "react";
+import { Button } from "./Button";
+export const MemoButton = memo(Button);"#,
+            ),
+            (
+                "src/FancyButton.tsx",
+                r#"import styled from "styled-components";
+import { MemoButton } from "./MemoButton";
+export const FancyButton = styled(MemoButton)`color: red;`;"#,
+            ),
+            // The definition is found through the re-export
+            (
+                "src/index.ts",
+                r#"export { FancyButton } from "./FancyButton";"#,
+            ),
+            (
+                "src/App.tsx",
+                r#"import { Button } from "./Button";
+import { MemoButton } from "./MemoButton";
+import { FancyButton } from "./index";
+export const App = () => (
+  <>
+    <Button variant="plain" />
+    <MemoButton variant="memo" />
+    <FancyButton variant="fancy" />
+    <FancyButton variant="fancy" />
+  </>
+);"#,
+            ),
+        ]);
+        let config = AnalyzerConfig {
+            include_native_elements: false,
+            roll_up_wrapped_usages: true,
+            ..AnalyzerConfig::default()
+        };
+        let report = crate::AnalysisService::new(config)
+            .run(project.root())
+            .unwrap();
+        let find = |name: &str| {
+            report
+                .components()
+                .iter()
+                .find(|c| c.display_name() == name)
+                .unwrap_or_else(|| panic!("{name} should be used"))
+        };
+
+        let fancy = find("FancyButton");
+        let kinds: Vec<&str> = fancy
+            .wraps()
+            .unwrap()
+            .kinds()
+            .iter()
+            .map(|k| k.name())
+            .collect();
+        assert_eq!(kinds, vec!["styled"]);
+        assert_eq!(
+            fancy.wraps().unwrap().inner(),
+            Some(find("MemoButton").identity())
+        );
+
+        // Button counts the usages of both wrappers, MemoButton only those of FancyButton
+        let button = find("Button");
+        assert_eq!(button.usages().len(), 1);
+        assert_eq!(button.statistics().total_count(), 4);
+        let mut rolled_up = button.rolled_up().to_vec();
+        rolled_up.sort();
+        let mut expected = vec![fancy.id().to_string(), find("MemoButton").id().to_string()];
+        expected.sort();
+        assert_eq!(rolled_up, expected);
+
+        assert_eq!(find("MemoButton").statistics().total_count(), 3);
+        assert_eq!(fancy.statistics().total_count(), 2);
+        assert!(fancy.rolled_up().is_empty());
+
+        let variants: Vec<Option<&str>> = button.statistics().prop_patterns()[0]
+            .distribution()
+            .iter()
+            .map(|d| d.value())
+            .collect();
+        assert_eq!(variants.len(), 3);
+    }
 }
@@ -0,0 +1,495 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::analyze::{ComponentSource, ComponentUsageAggregate, SimplifiedProp};
+use crate::result::AnalysisReport;
+use crate::rules::prop_matches;
+use crate::{AnalysisError, Result};
+
+/// Selects components by name and origin
+///
+/// `name` is matched against the export path (`Button`, `Menu.Item`) and the display name.
+/// `package` is matched against the package the component belongs to, and `path` against the
+/// project-relative path of internal components. A trailing `*` matches any suffix.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ComponentSelector {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+}
+
+impl ComponentSelector {
+    pub fn matches(&self, aggregate: &ComponentUsageAggregate) -> bool {
+        let identity = aggregate.identity();
+
+        let name_matches = matches_pattern(&self.name, &identity.display_name())
+            || matches_pattern(&self.name, aggregate.display_name());
+        if !name_matches {
+            return false;
+        }
+
+        if let Some(package) = &self.package {
+            let package_name = match identity.source() {
+                ComponentSource::External { package } => Some(package.name()),
+                ComponentSource::Internal { .. } => identity.package().map(|p| p.name()),
+                ComponentSource::Native => None,
+            };
+            if !package_name.is_some_and(|name| matches_pattern(package, name)) {
+                return false;
+            }
+        }
+
+        if let Some(path) = &self.path {
+            let ComponentSource::Internal { canonical_path } = identity.source() else {
+                return false;
+            };
+            if !matches_pattern(path, &canonical_path.replace('\\', "/")) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// Match `value` against `pattern`, where a trailing `*` matches any suffix
+pub(crate) fn matches_pattern(pattern: &str, value: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => value.starts_with(prefix),
+        None => pattern == value,
+    }
+}
+
+/// A prop (optionally with a specific value) that must not be used on a component
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BannedProp {
+    pub component: ComponentSelector,
+    pub prop: String,
+    /// Only ban this value; any value is banned when omitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+}
+
+impl BannedProp {
+    pub fn matches_prop(&self, prop: &SimplifiedProp) -> bool {
+        prop_matches(prop, &self.prop, self.value.as_deref())
+    }
+
+    fn label(&self, component_name: &str) -> String {
+        match &self.value {
+            Some(value) => format!("{component_name}[{}=\"{value}\"]", self.prop),
+            None => format!("{component_name}[{}]", self.prop),
+        }
+    }
+
+    fn key(&self, component_id: &str) -> String {
+        match &self.value {
+            Some(value) => format!("{component_id}:{}={value}", self.prop),
+            None => format!("{component_id}:{}", self.prop),
+        }
+    }
+}
+
+/// What the `check` mode enforces against the baseline
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CheckPolicy {
+    /// Components whose usage count must not grow
+    pub deprecated: Vec<ComponentSelector>,
+    /// Props whose usage count must not grow
+    pub banned_props: Vec<BannedProp>,
+    /// Packages from which no new components may be used
+    pub disallowed_packages: Vec<String>,
+}
+
+impl CheckPolicy {
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json)
+            .map_err(|e| AnalysisError::new(format!("Failed to parse check policy: {e}")))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    pub name: String,
+    pub count: usize,
+}
+
+/// Usage counts the project is allowed to have, keyed by component ID
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Baseline {
+    pub deprecated: BTreeMap<String, BaselineEntry>,
+    pub banned_props: BTreeMap<String, BaselineEntry>,
+    pub disallowed: BTreeMap<String, BaselineEntry>,
+}
+
+impl Baseline {
+    /// Measure the current usage of everything the policy covers
+    pub fn measure(report: &AnalysisReport, policy: &CheckPolicy) -> Self {
+        let mut baseline = Self::default();
+
+        for aggregate in report.components() {
+            let id = aggregate.id();
+            let name = aggregate.display_name();
+            let count = aggregate.usages().len();
+
+            if policy.deprecated.iter().any(|s| s.matches(aggregate)) {
+                baseline.deprecated.insert(
+                    id.to_string(),
+                    BaselineEntry {
+                        name: name.to_string(),
+                        count,
+                    },
+                );
+            }
+
+            for banned in policy
+                .banned_props
+                .iter()
+                .filter(|b| b.component.matches(aggregate))
+            {
+                let count = aggregate
+                    .usages()
+                    .iter()
+                    .filter(|u| u.simplified_props().iter().any(|p| banned.matches_prop(p)))
+                    .count();
+                if count > 0 {
+                    baseline.banned_props.insert(
+                        banned.key(id),
+                        BaselineEntry {
+                            name: banned.label(name),
+                            count,
+                        },
+                    );
+                }
+            }
+
+            if let ComponentSource::External { package } = aggregate.identity().source() {
+                if policy
+                    .disallowed_packages
+                    .iter()
+                    .any(|p| matches_pattern(p, package.name()))
+                {
+                    baseline.disallowed.insert(
+                        id.to_string(),
+                        BaselineEntry {
+                            name: format!("{name} ({})", package.name()),
+                            count,
+                        },
+                    );
+                }
+            }
+        }
+
+        baseline
+    }
+
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json)
+            .map_err(|e| AnalysisError::new(format!("Failed to parse baseline: {e}")))
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = std::fs::read_to_string(path).map_err(|e| {
+            AnalysisError::new(format!("Failed to read baseline {}: {e}", path.display()))
+        })?;
+        Self::from_json(&json)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json =
+            serde_json::to_string_pretty(self).map_err(|e| AnalysisError::new(e.to_string()))?;
+        std::fs::write(path, format!("{json}\n"))?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckViolationKind {
+    DeprecatedUsageGrew,
+    BannedPropUsageGrew,
+    DisallowedComponentAdded,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckViolation {
+    pub kind: CheckViolationKind,
+    pub name: String,
+    pub baseline_count: usize,
+    pub current_count: usize,
+}
+
+impl CheckViolation {
+    pub fn message(&self) -> String {
+        match self.kind {
+            CheckViolationKind::DeprecatedUsageGrew => format!(
+                "Usage of deprecated component `{}` grew from {} to {}",
+                self.name, self.baseline_count, self.current_count
+            ),
+            CheckViolationKind::BannedPropUsageGrew => format!(
+                "Usage of banned prop `{}` grew from {} to {}",
+                self.name, self.baseline_count, self.current_count
+            ),
+            CheckViolationKind::DisallowedComponentAdded => format!(
+                "New component `{}` from a disallowed package is used {} time(s)",
+                self.name, self.current_count
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckOutcome {
+    pub passed: bool,
+    pub violations: Vec<CheckViolation>,
+    /// Usage measured in this run, to be written with `--update-baseline`
+    pub current: Baseline,
+}
+
+/// Compare the current usage against the baseline
+///
+/// Counts may shrink freely; any growth, or a new component from a disallowed package, is a
+/// violation.
+pub fn check(report: &AnalysisReport, policy: &CheckPolicy, baseline: &Baseline) -> CheckOutcome {
+    let current = Baseline::measure(report, policy);
+    let violations = compare(baseline, &current);
+
+    CheckOutcome {
+        passed: violations.is_empty(),
+        violations,
+        current,
+    }
+}
+
+fn compare(baseline: &Baseline, current: &Baseline) -> Vec<CheckViolation> {
+    let grown = |base: &BTreeMap<String, BaselineEntry>,
+                 now: &BTreeMap<String, BaselineEntry>,
+                 kind: CheckViolationKind| {
+        now.iter()
+            .filter_map(|(key, entry)| {
+                let baseline_count = base.get(key).map_or(0, |e| e.count);
+                (entry.count > baseline_count).then(|| CheckViolation {
+                    kind,
+                    name: entry.name.clone(),
+                    baseline_count,
+                    current_count: entry.count,
+                })
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut violations = grown(
+        &baseline.deprecated,
+        &current.deprecated,
+        CheckViolationKind::DeprecatedUsageGrew,
+    );
+    violations.extend(grown(
+        &baseline.banned_props,
+        &current.banned_props,
+        CheckViolationKind::BannedPropUsageGrew,
+    ));
+    violations.extend(
+        current
+            .disallowed
+            .iter()
+            .filter(|(id, _)| !baseline.disallowed.contains_key(*id))
+            .map(|(_, entry)| CheckViolation {
+                kind: CheckViolationKind::DisallowedComponentAdded,
+                name: entry.name.clone(),
+                baseline_count: 0,
+                current_count: entry.count,
+            }),
+    );
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AnalysisService;
+    use crate::testing::TestProject;
+
+    fn entry(name: &str, count: usize) -> BaselineEntry {
+        BaselineEntry {
+            name: name.to_string(),
+            count,
+        }
+    }
+
+    #[test]
+    fn test_matches_pattern() {
+        assert!(matches_pattern("@acme/*", "@acme/ui"));
+        assert!(matches_pattern("Button", "Button"));
+        assert!(!matches_pattern("Button", "ButtonGroup"));
+    }
+
+    #[test]
+    fn test_compare_ratchets() {
+        let mut baseline = Baseline::default();
+        baseline
+            .deprecated
+            .insert("a".to_string(), entry("LegacyModal", 3));
+        baseline
+            .disallowed
+            .insert("b".to_string(), entry("Old (@old/ui)", 1));
+
+        let mut current = baseline.clone();
+        // Shrinking usage is fine
+        current
+            .deprecated
+            .insert("a".to_string(), entry("LegacyModal", 2));
+        assert!(compare(&baseline, &current).is_empty());
+
+        current
+            .deprecated
+            .insert("a".to_string(), entry("LegacyModal", 4));
+        current
+            .banned_props
+            .insert("c:variant=danger".to_string(), entry("Button", 1));
+        current
+            .disallowed
+            .insert("d".to_string(), entry("New (@old/ui)", 1));
+
+        let kinds: Vec<_> = compare(&baseline, &current)
+            .into_iter()
+            .map(|v| v.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                CheckViolationKind::DeprecatedUsageGrew,
+                CheckViolationKind::BannedPropUsageGrew,
+                CheckViolationKind::DisallowedComponentAdded,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_analyzed_project() {
+        let app = |body: &str| {
+            format!(
+                r#"import {{ Button, LegacyModal }} from "@acme/ui";
+import {{ Banner, Dialog }} from "@old/ui";
+import {{ Local }} from "./Local";
+
+export const App = () => (
+  <>
+{body}
+  </>
+);"#
+            )
+        };
+        let project = TestProject::new(&[
+            (".ignore", "node_modules"),
+            // Internal components belong to a package matching the disallowed pattern
+            ("package.json", r#"{"name":"@old/app","version":"1.0.0"}"#),
+            (
+                "node_modules/@acme/ui/package.json",
+                r#"{"name":"@acme/ui","version":"1.0.0","main":"index.js"}"#,
+            ),
+            (
+                "node_modules/@acme/ui/index.js",
+                "export const Button = () => null;\nexport const LegacyModal = () => null;",
+            ),
+            (
+                "node_modules/@old/ui/package.json",
+                r#"{"name":"@old/ui","version":"1.0.0","main":"index.js"}"#,
+            ),
+            (
+                "node_modules/@old/ui/index.js",
+                "export const Banner = () => null;\nexport const Dialog = () => null;",
+            ),
+            (
+                "src/Local.tsx",
+                "export const Local = (props) => <span {...props} />;",
+            ),
+            (
+                "src/App.tsx",
+                &app(r#"    <LegacyModal />
+    <Button variant="danger" />
+    <Button variant="primary" />
+    <Banner />
+    <Local variant="danger" tone="dark" />"#),
+            ),
+        ]);
+        let policy = CheckPolicy::from_json(
+            r#"{
+                "deprecated": [{ "name": "LegacyModal" }],
+                "banned_props": [
+                    { "component": { "name": "Button", "package": "@acme/ui" },
+                      "prop": "variant", "value": "danger" },
+                    { "component": { "name": "Button", "package": "@acme/ui" }, "prop": "tone" }
+                ],
+                "disallowed_packages": ["@old/*"]
+            }"#,
+        )
+        .unwrap();
+        let analyze = || AnalysisService::default().run(project.root()).unwrap();
+
+        let baseline = check(&analyze(), &policy, &Baseline::default()).current;
+        let names = |entries: &BTreeMap<String, BaselineEntry>| -> Vec<(String, usize)> {
+            entries
+                .values()
+                .map(|e| (e.name.clone(), e.count))
+                .collect()
+        };
+        assert_eq!(
+            names(&baseline.deprecated),
+            [("LegacyModal".to_string(), 1)]
+        );
+        // Only usages with the banned value count, and `Local` is not selected
+        assert_eq!(
+            names(&baseline.banned_props),
+            [("Button[variant=\"danger\"]".to_string(), 1)]
+        );
+        // `Local` comes from `@old/app` but is internal, so only `Banner` is disallowed
+        assert_eq!(
+            names(&baseline.disallowed),
+            [("Banner (@old/ui)".to_string(), 1)]
+        );
+
+        std::fs::write(
+            project.root().join("src/App.tsx"),
+            app(r#"    <LegacyModal />
+    <LegacyModal />
+    <Button variant="danger" />
+    <Button tone="dark" />
+    <Banner />
+    <Dialog />
+    <Local variant="danger" tone="dark" />"#),
+        )
+        .unwrap();
+        let outcome = check(&analyze(), &policy, &baseline);
+
+        assert!(!outcome.passed);
+        let violations: Vec<(CheckViolationKind, &str, usize, usize)> = outcome
+            .violations
+            .iter()
+            .map(|v| (v.kind, v.name.as_str(), v.baseline_count, v.current_count))
+            .collect();
+        assert_eq!(
+            violations,
+            [
+                (CheckViolationKind::DeprecatedUsageGrew, "LegacyModal", 1, 2),
+                (
+                    CheckViolationKind::BannedPropUsageGrew,
+                    "Button[tone]",
+                    0,
+                    1
+                ),
+                (
+                    CheckViolationKind::DisallowedComponentAdded,
+                    "Dialog (@old/ui)",
+                    0,
+                    1
+                ),
+            ]
+        );
+    }
+}
@@ -1,8 +1,32 @@
-#[derive(Debug, Clone)]
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::analyze::IdOptions;
+use crate::rules::Rule;
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct AnalyzerConfig {
     pub target_extensions: Vec<String>,
     pub include_native_elements: bool,
     pub cache_enabled: bool,
+    /// Inspect component definitions for wrappers such as `styled(Button)` or `memo(Button)`
+    ///
+    /// Off by default since every resolved definition file is parsed again.
+    pub detect_wrappers: bool,
+    /// Count usages of wrapped components in the statistics of the component they wrap
+    ///
+    /// Enables `detect_wrappers`.
+    pub roll_up_wrapped_usages: bool,
+    /// Count `React.createElement(Button, props)` and `_jsx(Button, props)` calls as usages
+    pub collect_create_element_calls: bool,
+    /// Dependency packages in `node_modules` to analyze as well, e.g. `@acme/*`
+    ///
+    /// Usages found in them are attributed to the dependency package.
+    pub dependency_packages: Vec<String>,
+    /// How component IDs are generated
+    pub id_options: IdOptions,
+    /// Usage rules evaluated after aggregation
+    pub rules: Vec<Rule>,
 }
 
 impl Default for AnalyzerConfig {
@@ -13,9 +37,18 @@ impl Default for AnalyzerConfig {
                 "jsx".to_string(),
                 "ts".to_string(),
                 "js".to_string(),
+                "mdx".to_string(),
+                "astro".to_string(),
+                "vue".to_string(),
             ],
             include_native_elements: true,
             cache_enabled: true,
+            detect_wrappers: false,
+            roll_up_wrapped_usages: false,
+            collect_create_element_calls: true,
+            dependency_packages: Vec::new(),
+            id_options: IdOptions::default(),
+            rules: Vec::new(),
         }
     }
 }
@@ -0,0 +1,462 @@
+use oxc::ast::ast::{Expression, Program, Statement};
+use oxc::{allocator::Allocator, parser::Parser, span::SourceType};
+use oxc_syntax::module_record::{
+    ExportExportName, ExportImportName, ImportImportName, ModuleRecord,
+};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::analyze::{AnalysisContext, ComponentIdentity, IdOptions};
+use crate::parser::{ModuleSpecifier, SourceFile, extract_imports};
+use crate::resolver::{Cache, ConcurrentCache};
+
+/// Maximum number of re-exports followed to reach a definition
+const MAX_REEXPORT_DEPTH: usize = 5;
+
+/// Call that derives a component from another one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WrapperKind {
+    /// `styled(Button)`, `styled.button`
+    Styled,
+    /// `forwardRef(...)`
+    ForwardRef,
+    /// `memo(...)`
+    Memo,
+    /// `withRouter(Button)`, `connect(mapState)(Button)`
+    Hoc(String),
+    /// `cva(...)`, `tv(...)`
+    Variants(String),
+}
+
+impl WrapperKind {
+    pub fn name(&self) -> &str {
+        match self {
+            WrapperKind::Styled => "styled",
+            WrapperKind::ForwardRef => "forwardRef",
+            WrapperKind::Memo => "memo",
+            WrapperKind::Hoc(name) | WrapperKind::Variants(name) => name,
+        }
+    }
+}
+
+/// Wrapper calls found in a component definition, outermost first
+#[derive(Debug, Clone)]
+pub struct ComponentWrapping {
+    kinds: Vec<WrapperKind>,
+    inner: Option<ComponentIdentity>,
+}
+
+impl ComponentWrapping {
+    pub fn new(kinds: Vec<WrapperKind>, inner: Option<ComponentIdentity>) -> Self {
+        Self { kinds, inner }
+    }
+
+    pub fn kinds(&self) -> &[WrapperKind] {
+        &self.kinds
+    }
+
+    /// The component being wrapped, if it could be identified
+    pub fn inner(&self) -> Option<&ComponentIdentity> {
+        self.inner.as_ref()
+    }
+
+    pub fn to_serializable(&self, id_options: IdOptions) -> SerializableWrapping {
+        SerializableWrapping {
+            kinds: self.kinds.iter().map(|k| k.name().to_string()).collect(),
+            inner_id: self.inner.as_ref().map(|i| i.generate_id_with(id_options)),
+            inner_name: self.inner.as_ref().map(|i| i.display_name()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct SerializableWrapping {
+    pub kinds: Vec<String>,
+    pub inner_id: Option<String>,
+    pub inner_name: Option<String>,
+}
+
+/// Reads component definitions to find wrapper calls
+pub struct DefinitionInspector {
+    cache: ConcurrentCache<(PathBuf, String), Option<ComponentWrapping>>,
+}
+
+impl DefinitionInspector {
+    pub fn new() -> Self {
+        Self {
+            cache: ConcurrentCache::new(),
+        }
+    }
+
+    /// Inspect the definition of `export` in the module at `path`
+    pub fn inspect(
+        &self,
+        context: &AnalysisContext,
+        path: &Path,
+        export: &str,
+    ) -> Option<ComponentWrapping> {
+        let key = (path.to_path_buf(), export.to_string());
+        if let Some(cached) = self.cache.get(&key) {
+            return cached;
+        }
+
+        let wrapping = inspect_module(context, path, export, MAX_REEXPORT_DEPTH);
+        self.cache.insert(key, wrapping.clone());
+        wrapping
+    }
+}
+
+impl Default for DefinitionInspector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Find the definition of `export`, following re-exports up to `depth` modules
+fn inspect_module(
+    context: &AnalysisContext,
+    path: &Path,
+    export: &str,
+    depth: usize,
+) -> Option<ComponentWrapping> {
+    if depth == 0 {
+        return None;
+    }
+
+    let source_text = std::fs::read_to_string(path).ok()?;
+    let source_file = SourceFile::new(path, context.project_context().root()).ok()?;
+    let source_type = SourceType::from_path(path)
+        .unwrap_or_default()
+        .with_jsx(true);
+
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, &source_text, source_type).parse();
+
+    match find_export(&ret.program, &ret.module_record, export)? {
+        ExportTarget::Expression(expr) => {
+            let (kinds, inner) = unwrap_wrappers(expr);
+            if kinds.is_empty() {
+                return None;
+            }
+            let inner = inner
+                .and_then(|inner| identify_inner(context, &source_file, &ret.module_record, inner));
+            Some(ComponentWrapping::new(kinds, inner))
+        }
+        ExportTarget::Reexport { specifier, name } => {
+            let resolved = context
+                .module_resolver()
+                .resolve(&ModuleSpecifier::new(specifier), &source_file)
+                .ok()?;
+            inspect_module(context, resolved.canonical_path(), &name, depth - 1)
+        }
+        ExportTarget::Star(specifiers) => specifiers.into_iter().find_map(|specifier| {
+            let resolved = context
+                .module_resolver()
+                .resolve(&ModuleSpecifier::new(specifier), &source_file)
+                .ok()?;
+            inspect_module(context, resolved.canonical_path(), export, depth - 1)
+        }),
+    }
+}
+
+/// Where an export gets its value from
+enum ExportTarget<'p, 'a> {
+    /// Expression assigned in this module
+    Expression(&'p Expression<'a>),
+    /// `export { name } from "specifier"` or an exported import
+    Reexport { specifier: String, name: String },
+    /// `export * from "specifier"`
+    Star(Vec<String>),
+}
+
+/// Reference to the component passed to a wrapper
+enum InnerRef {
+    Identifier(String),
+    Native(String),
+    Unknown,
+}
+
+fn find_export<'p, 'a>(
+    program: &'p Program<'a>,
+    record: &ModuleRecord,
+    export: &str,
+) -> Option<ExportTarget<'p, 'a>> {
+    if export == "default" {
+        for stmt in &program.body {
+            if let Statement::ExportDefaultDeclaration(decl) = stmt {
+                let expr = decl.declaration.as_expression()?;
+                if let Expression::Identifier(id) = expr.get_inner_expression() {
+                    return find_local(program, record, &id.name);
+                }
+                return Some(ExportTarget::Expression(expr));
+            }
+        }
+    }
+
+    let matches_export = |name: &ExportExportName| match name {
+        ExportExportName::Name(name) => name.name == export,
+        ExportExportName::Default(_) => export == "default",
+        ExportExportName::Null => false,
+    };
+
+    if let Some(entry) = record
+        .local_export_entries
+        .iter()
+        .find(|entry| matches_export(&entry.export_name))
+    {
+        let local = entry.local_name.name()?;
+        return find_local(program, record, &local);
+    }
+
+    if let Some(entry) = record
+        .indirect_export_entries
+        .iter()
+        .find(|entry| matches_export(&entry.export_name))
+    {
+        let ExportImportName::Name(name) = &entry.import_name else {
+            return None;
+        };
+        return Some(ExportTarget::Reexport {
+            specifier: entry.module_request.as_ref()?.name.to_string(),
+            name: name.name.to_string(),
+        });
+    }
+
+    if export == "default" {
+        return None;
+    }
+
+    let specifiers: Vec<String> = record
+        .star_export_entries
+        .iter()
+        .filter_map(|entry| entry.module_request.as_ref())
+        .map(|request| request.name.to_string())
+        .collect();
+    (!specifiers.is_empty()).then_some(ExportTarget::Star(specifiers))
+}
+
+/// Find the initializer of a top-level variable, or the import it comes from
+fn find_local<'p, 'a>(
+    program: &'p Program<'a>,
+    record: &ModuleRecord,
+    local: &str,
+) -> Option<ExportTarget<'p, 'a>> {
+    use oxc::ast::ast::{BindingPatternKind, Declaration};
+
+    let declarations = program.body.iter().filter_map(|stmt| match stmt {
+        Statement::VariableDeclaration(decl) => Some(&**decl),
+        Statement::ExportNamedDeclaration(export) => match &export.declaration {
+            Some(Declaration::VariableDeclaration(decl)) => Some(&**decl),
+            _ => None,
+        },
+        _ => None,
+    });
+
+    for decl in declarations {
+        for declarator in &decl.declarations {
+            if let BindingPatternKind::BindingIdentifier(id) = &declarator.id.kind {
+                if id.name == local {
+                    return declarator.init.as_ref().map(ExportTarget::Expression);
+                }
+            }
+        }
+    }
+
+    let entry = record
+        .import_entries
+        .iter()
+        .find(|entry| entry.local_name.name == local)?;
+    let name = match &entry.import_name {
+        ImportImportName::Name(name) => name.name.to_string(),
+        ImportImportName::Default(_) => "default".to_string(),
+        ImportImportName::NamespaceObject => return None,
+    };
+    Some(ExportTarget::Reexport {
+        specifier: entry.module_request.name.to_string(),
+        name,
+    })
+}
+
+/// Peel wrapper calls off an expression, returning them outermost first
+fn unwrap_wrappers(expr: &Expression) -> (Vec<WrapperKind>, Option<InnerRef>) {
+    let mut kinds = Vec::new();
+    let mut current = expr;
+
+    loop {
+        current = current.get_inner_expression();
+        match current {
+            // styled(Button)`...`, styled.button`...`
+            Expression::TaggedTemplateExpression(tagged) => {
+                let inner = styled_target(&tagged.tag);
+                if inner.is_some() {
+                    kinds.push(WrapperKind::Styled);
+                }
+                return (kinds, inner);
+            }
+            Expression::CallExpression(call) => {
+                // styled(Button)({ ... })
+                if let Some(inner) = styled_target(&call.callee) {
+                    kinds.push(WrapperKind::Styled);
+                    return (kinds, Some(inner));
+                }
+
+                let kind = match call.callee_name() {
+                    Some("memo") => WrapperKind::Memo,
+                    Some("forwardRef") => WrapperKind::ForwardRef,
+                    Some(name @ ("cva" | "tv")) => {
+                        kinds.push(WrapperKind::Variants(name.to_string()));
+                        return (kinds, None);
+                    }
+                    Some(name) if is_hoc_name(name) => WrapperKind::Hoc(name.to_string()),
+                    _ => match call.callee.get_inner_expression() {
+                        // withStyles(styles)(Button), connect(mapState)(Button)
+                        Expression::CallExpression(factory) => match factory.callee_name() {
+                            Some(name) if is_hoc_name(name) => WrapperKind::Hoc(name.to_string()),
+                            _ => return (kinds, None),
+                        },
+                        _ => return (kinds, None),
+                    },
+                };
+                kinds.push(kind);
+
+                match call.arguments.first().and_then(|arg| arg.as_expression()) {
+                    Some(arg) => current = arg,
+                    None => return (kinds, Some(InnerRef::Unknown)),
+                }
+            }
+            Expression::Identifier(id) if !kinds.is_empty() => {
+                return (kinds, Some(InnerRef::Identifier(id.name.to_string())));
+            }
+            _ if !kinds.is_empty() => return (kinds, Some(InnerRef::Unknown)),
+            _ => return (kinds, None),
+        }
+    }
+}
+
+/// Return the styled target if the expression is `styled(X)`, `styled.tag` or
+/// `.attrs()` / `.withConfig()` chained on one of them
+fn styled_target(expr: &Expression) -> Option<InnerRef> {
+    match expr.get_inner_expression() {
+        Expression::CallExpression(call) => match call.callee.get_inner_expression() {
+            Expression::Identifier(id) if id.name == "styled" => {
+                match call.arguments.first().and_then(|arg| arg.as_expression()) {
+                    Some(Expression::Identifier(inner)) => {
+                        Some(InnerRef::Identifier(inner.name.to_string()))
+                    }
+                    Some(Expression::StringLiteral(tag)) => {
+                        Some(InnerRef::Native(tag.value.to_string()))
+                    }
+                    _ => Some(InnerRef::Unknown),
+                }
+            }
+            Expression::StaticMemberExpression(member)
+                if matches!(member.property.name.as_str(), "attrs" | "withConfig") =>
+            {
+                styled_target(&member.object)
+            }
+            _ => None,
+        },
+        Expression::StaticMemberExpression(member) => match member.object.get_inner_expression() {
+            Expression::Identifier(id) if id.name == "styled" => {
+                Some(InnerRef::Native(member.property.name.to_string()))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// `withRouter`, `withStyles`, `connect`, ...
+fn is_hoc_name(name: &str) -> bool {
+    name == "connect"
+        || name
+            .strip_prefix("with")
+            .and_then(|rest| rest.chars().next())
+            .is_some_and(|c| c.is_uppercase())
+}
+
+fn identify_inner(
+    context: &AnalysisContext,
+    source_file: &SourceFile,
+    record: &ModuleRecord,
+    inner: InnerRef,
+) -> Option<ComponentIdentity> {
+    match inner {
+        InnerRef::Native(tag) => Some(ComponentIdentity::native(tag)),
+        InnerRef::Identifier(name) => {
+            let imports = extract_imports(record);
+            if let Some(binding) = imports.iter().find(|b| b.local_name() == name) {
+                return context
+                    .resolve_identity(binding, source_file, &[])
+                    .map(|(identity, _)| identity);
+            }
+
+            // Declared in the same module: use its export name when it has one
+            let export = record
+                .local_export_entries
+                .iter()
+                .find(|entry| entry.local_name.name().is_some_and(|local| local == name))
+                .map(|entry| match &entry.export_name {
+                    ExportExportName::Name(export) => export.name.to_string(),
+                    _ => "default".to_string(),
+                })
+                .unwrap_or(name);
+            context.local_identity(source_file.canonical(), &export)
+        }
+        InnerRef::Unknown => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unwrap_source(source: &str) -> (Vec<WrapperKind>, Option<InnerRef>) {
+        let allocator = Allocator::default();
+        let source_type = SourceType::tsx();
+        let ret = Parser::new(&allocator, source, source_type).parse();
+        let Some(ExportTarget::Expression(expr)) =
+            find_export(&ret.program, &ret.module_record, "Derived")
+        else {
+            panic!("Derived should be found");
+        };
+        unwrap_wrappers(expr)
+    }
+
+    #[test]
+    fn test_unwrap_styled_and_memo() {
+        let (kinds, inner) =
+            unwrap_source("export const Derived = memo(styled(Button)`color: red;`);");
+        assert_eq!(kinds, vec![WrapperKind::Memo, WrapperKind::Styled]);
+        assert!(matches!(inner, Some(InnerRef::Identifier(name)) if name == "Button"));
+
+        let (kinds, inner) =
+            unwrap_source("const Derived = styled.button`color: red;`;\nexport { Derived };");
+        assert_eq!(kinds, vec![WrapperKind::Styled]);
+        assert!(matches!(inner, Some(InnerRef::Native(tag)) if tag == "button"));
+    }
+
+    #[test]
+    fn test_unwrap_hoc_and_forward_ref() {
+        let (kinds, inner) =
+            unwrap_source("export const Derived = connect(mapState)(withRouter(Page));");
+        assert_eq!(
+            kinds,
+            vec![
+                WrapperKind::Hoc("connect".to_string()),
+                WrapperKind::Hoc("withRouter".to_string())
+            ]
+        );
+        assert!(matches!(inner, Some(InnerRef::Identifier(name)) if name == "Page"));
+
+        let (kinds, inner) = unwrap_source(
+            "export const Derived = React.forwardRef((props, ref) => <input ref={ref} />);",
+        );
+        assert_eq!(kinds, vec![WrapperKind::ForwardRef]);
+        assert!(matches!(inner, Some(InnerRef::Unknown)));
+
+        let (kinds, _) = unwrap_source("export const Derived = Button;");
+        assert!(kinds.is_empty());
+    }
+}
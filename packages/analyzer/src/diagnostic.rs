@@ -0,0 +1,43 @@
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::parser::{SourceFile, Span};
+use crate::rules::Severity;
+
+/// A problem the analyzer ran into, reported alongside the results
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// Stable identifier of the kind of problem, e.g. `parse-error`
+    pub code: String,
+    pub message: String,
+    pub file_path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub span: Option<Span>,
+}
+
+impl Diagnostic {
+    /// The file has syntax errors; usages after the error may be missing
+    ///
+    /// Files without `<`, `createElement` or `jsx` text are not parsed and never report one.
+    pub fn parse_error(file: &SourceFile, message: impl Into<String>, span: Option<Span>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            code: "parse-error".to_string(),
+            message: message.into(),
+            file_path: file.display_path(),
+            span,
+        }
+    }
+
+    /// The file could not be analyzed at all
+    pub fn analysis_failed(file_path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Error,
+            code: "analysis-failed".to_string(),
+            message: message.into(),
+            file_path: file_path.into(),
+            span: None,
+        }
+    }
+}
@@ -0,0 +1,484 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+use crate::export::escape_cell;
+use crate::result::AnalysisReport;
+
+/// Minimal view of a report needed for diffing
+///
+/// Field names follow the serialized report, so a stored report JSON can be loaded directly.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReportSnapshot {
+    #[serde(default)]
+    pub components: Vec<ComponentSnapshot>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentSnapshot {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub instances: Vec<InstanceSnapshot>,
+    #[serde(default)]
+    pub props_usages: Vec<PropUsageSnapshot>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstanceSnapshot {
+    pub file_path: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PropUsageSnapshot {
+    pub key: String,
+    #[serde(default)]
+    pub distribution: Vec<PropValueSnapshot>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PropValueSnapshot {
+    #[serde(default)]
+    pub value: Option<String>,
+    pub raw: String,
+    pub prop_type: String,
+    pub count: u32,
+}
+
+impl ReportSnapshot {
+    pub fn from_report(report: &AnalysisReport) -> Self {
+        let components = report
+            .components()
+            .iter()
+            .map(|aggregate| ComponentSnapshot {
+                id: aggregate.id().to_string(),
+                name: aggregate.display_name().to_string(),
+                instances: aggregate
+                    .usages()
+                    .iter()
+                    .map(|usage| InstanceSnapshot {
+                        file_path: usage.occurrence().location().file().display_path(),
+                    })
+                    .collect(),
+                props_usages: aggregate
+                    .statistics()
+                    .prop_patterns()
+                    .iter()
+                    .map(|pattern| PropUsageSnapshot {
+                        key: pattern.key().to_string(),
+                        distribution: pattern
+                            .distribution()
+                            .iter()
+                            .map(|d| PropValueSnapshot {
+                                value: d.value().map(|v| v.to_string()),
+                                raw: d.raw().to_string(),
+                                prop_type: d.value_pattern().to_string(),
+                                count: d.count() as u32,
+                            })
+                            .collect(),
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        Self { components }
+    }
+
+    /// Load a snapshot from serialized report JSON
+    pub fn from_json(json: &str) -> crate::Result<Self> {
+        serde_json::from_str(json)
+            .map_err(|e| crate::AnalysisError::new(format!("Failed to parse report: {e}")))
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReportDiff {
+    pub added: Vec<ComponentSummary>,
+    pub removed: Vec<ComponentSummary>,
+    pub changed: Vec<ComponentDiff>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ComponentSummary {
+    pub id: String,
+    pub name: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ComponentDiff {
+    pub id: String,
+    pub name: String,
+    pub base_count: usize,
+    pub head_count: usize,
+    pub props: Vec<PropDiff>,
+    pub files: Vec<FileCountChange>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeStatus {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PropDiff {
+    pub key: String,
+    pub status: ChangeStatus,
+    pub values: Vec<PropValueChange>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PropValueChange {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    pub raw: String,
+    pub prop_type: String,
+    pub base_count: u32,
+    pub head_count: u32,
+}
+
+/// Change in the number of instances in a file
+#[derive(Debug, Clone, Serialize)]
+pub struct FileCountChange {
+    pub file_path: String,
+    pub base_count: usize,
+    pub head_count: usize,
+}
+
+/// Compare two reports
+pub fn diff_reports(base: &AnalysisReport, head: &AnalysisReport) -> ReportDiff {
+    diff_snapshots(
+        &ReportSnapshot::from_report(base),
+        &ReportSnapshot::from_report(head),
+    )
+}
+
+/// Compare two report snapshots, matching components by ID
+pub fn diff_snapshots(base: &ReportSnapshot, head: &ReportSnapshot) -> ReportDiff {
+    let base_by_id: BTreeMap<&str, &ComponentSnapshot> =
+        base.components.iter().map(|c| (c.id.as_str(), c)).collect();
+    let head_by_id: BTreeMap<&str, &ComponentSnapshot> =
+        head.components.iter().map(|c| (c.id.as_str(), c)).collect();
+
+    let mut diff = ReportDiff::default();
+
+    for (id, component) in &head_by_id {
+        match base_by_id.get(id) {
+            None => diff.added.push(summarize(component)),
+            Some(base_component) => {
+                if let Some(changed) = diff_component(base_component, component) {
+                    diff.changed.push(changed);
+                }
+            }
+        }
+    }
+    for (id, component) in &base_by_id {
+        if !head_by_id.contains_key(id) {
+            diff.removed.push(summarize(component));
+        }
+    }
+
+    diff.added.sort_by(|a, b| a.name.cmp(&b.name));
+    diff.removed.sort_by(|a, b| a.name.cmp(&b.name));
+    diff.changed.sort_by(|a, b| a.name.cmp(&b.name));
+    diff
+}
+
+fn summarize(component: &ComponentSnapshot) -> ComponentSummary {
+    ComponentSummary {
+        id: component.id.clone(),
+        name: component.name.clone(),
+        count: component.instances.len(),
+    }
+}
+
+fn diff_component(base: &ComponentSnapshot, head: &ComponentSnapshot) -> Option<ComponentDiff> {
+    let props = diff_props(&base.props_usages, &head.props_usages);
+    let files = diff_files(&base.instances, &head.instances);
+
+    if base.instances.len() == head.instances.len() && props.is_empty() && files.is_empty() {
+        return None;
+    }
+
+    Some(ComponentDiff {
+        id: head.id.clone(),
+        name: head.name.clone(),
+        base_count: base.instances.len(),
+        head_count: head.instances.len(),
+        props,
+        files,
+    })
+}
+
+type ValueKey = (String, Option<String>, String);
+
+fn value_counts(usage: Option<&PropUsageSnapshot>) -> BTreeMap<ValueKey, u32> {
+    let mut counts = BTreeMap::new();
+    for d in usage.into_iter().flat_map(|u| u.distribution.iter()) {
+        *counts
+            .entry((d.prop_type.clone(), d.value.clone(), d.raw.clone()))
+            .or_insert(0) += d.count;
+    }
+    counts
+}
+
+fn diff_props(base: &[PropUsageSnapshot], head: &[PropUsageSnapshot]) -> Vec<PropDiff> {
+    let keys: BTreeSet<&str> = base
+        .iter()
+        .chain(head.iter())
+        .map(|p| p.key.as_str())
+        .collect();
+
+    keys.into_iter()
+        .filter_map(|key| {
+            let base_usage = base.iter().find(|p| p.key == key);
+            let head_usage = head.iter().find(|p| p.key == key);
+            let base_counts = value_counts(base_usage);
+            let head_counts = value_counts(head_usage);
+
+            let value_keys: BTreeSet<&ValueKey> =
+                base_counts.keys().chain(head_counts.keys()).collect();
+            let values: Vec<PropValueChange> = value_keys
+                .into_iter()
+                .filter_map(|value_key| {
+                    let base_count = base_counts.get(value_key).copied().unwrap_or(0);
+                    let head_count = head_counts.get(value_key).copied().unwrap_or(0);
+                    (base_count != head_count).then(|| PropValueChange {
+                        value: value_key.1.clone(),
+                        raw: value_key.2.clone(),
+                        prop_type: value_key.0.clone(),
+                        base_count,
+                        head_count,
+                    })
+                })
+                .collect();
+
+            let status = match (base_usage, head_usage) {
+                (None, Some(_)) => ChangeStatus::Added,
+                (Some(_), None) => ChangeStatus::Removed,
+                _ if values.is_empty() => return None,
+                _ => ChangeStatus::Changed,
+            };
+
+            Some(PropDiff {
+                key: key.to_string(),
+                status,
+                values,
+            })
+        })
+        .collect()
+}
+
+fn diff_files(base: &[InstanceSnapshot], head: &[InstanceSnapshot]) -> Vec<FileCountChange> {
+    let count = |instances: &[InstanceSnapshot]| {
+        let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+        for instance in instances {
+            *counts.entry(instance.file_path.clone()).or_insert(0) += 1;
+        }
+        counts
+    };
+    let base_counts = count(base);
+    let head_counts = count(head);
+
+    let files: BTreeSet<&String> = base_counts.keys().chain(head_counts.keys()).collect();
+    files
+        .into_iter()
+        .filter_map(|file| {
+            let base_count = base_counts.get(file).copied().unwrap_or(0);
+            let head_count = head_counts.get(file).copied().unwrap_or(0);
+            (base_count != head_count).then(|| FileCountChange {
+                file_path: file.clone(),
+                base_count,
+                head_count,
+            })
+        })
+        .collect()
+}
+
+impl ReportDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// Render a Markdown summary suitable for a pull request comment
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::from("## Component usage changes\n\n");
+
+        if self.is_empty() {
+            md.push_str("No changes in component usage.\n");
+            return md;
+        }
+
+        if !self.added.is_empty() {
+            let _ = writeln!(md, "### Added components ({})\n", self.added.len());
+            md.push_str("| Component | Usages |\n| --- | ---: |\n");
+            for c in &self.added {
+                let _ = writeln!(md, "| `{}` | {} |", escape_cell(&c.name), c.count);
+            }
+            md.push('\n');
+        }
+
+        if !self.removed.is_empty() {
+            let _ = writeln!(md, "### Removed components ({})\n", self.removed.len());
+            md.push_str("| Component | Usages |\n| --- | ---: |\n");
+            for c in &self.removed {
+                let _ = writeln!(md, "| `{}` | {} |", escape_cell(&c.name), c.count);
+            }
+            md.push('\n');
+        }
+
+        if !self.changed.is_empty() {
+            let _ = writeln!(md, "### Changed components ({})\n", self.changed.len());
+            md.push_str("| Component | Base | Head | Δ |\n| --- | ---: | ---: | ---: |\n");
+            for c in &self.changed {
+                let _ = writeln!(
+                    md,
+                    "| `{}` | {} | {} | {} |",
+                    escape_cell(&c.name),
+                    c.base_count,
+                    c.head_count,
+                    format_delta(c.base_count as i64, c.head_count as i64)
+                );
+            }
+            md.push('\n');
+
+            for c in &self.changed {
+                if c.props.is_empty() && c.files.is_empty() {
+                    continue;
+                }
+                let _ = writeln!(
+                    md,
+                    "<details><summary><code>{}</code></summary>\n",
+                    escape_html(&c.name)
+                );
+                if !c.props.is_empty() {
+                    md.push_str("| Prop | Value | Type | Base | Head |\n| --- | --- | --- | ---: | ---: |\n");
+                    for prop in &c.props {
+                        let status = match prop.status {
+                            ChangeStatus::Added => " (new)",
+                            ChangeStatus::Removed => " (removed)",
+                            ChangeStatus::Changed => "",
+                        };
+                        for value in &prop.values {
+                            let _ = writeln!(
+                                md,
+                                "| `{}`{} | `{}` | {} | {} | {} |",
+                                escape_cell(&prop.key),
+                                status,
+                                escape_cell(value.value.as_deref().unwrap_or(&value.raw)),
+                                value.prop_type,
+                                value.base_count,
+                                value.head_count
+                            );
+                        }
+                    }
+                    md.push('\n');
+                }
+                if !c.files.is_empty() {
+                    md.push_str("| File | Base | Head |\n| --- | ---: | ---: |\n");
+                    for file in &c.files {
+                        let _ = writeln!(
+                            md,
+                            "| `{}` | {} | {} |",
+                            escape_cell(&file.file_path),
+                            file.base_count,
+                            file.head_count
+                        );
+                    }
+                    md.push('\n');
+                }
+                md.push_str("</details>\n\n");
+            }
+        }
+
+        md
+    }
+}
+
+fn format_delta(base: i64, head: i64) -> String {
+    let delta = head - base;
+    if delta > 0 {
+        format!("+{delta}")
+    } else {
+        delta.to_string()
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn component(id: &str, files: &[&str], variants: &[(&str, u32)]) -> ComponentSnapshot {
+        ComponentSnapshot {
+            id: id.to_string(),
+            name: id.to_string(),
+            instances: files
+                .iter()
+                .map(|f| InstanceSnapshot {
+                    file_path: f.to_string(),
+                })
+                .collect(),
+            props_usages: vec![PropUsageSnapshot {
+                key: "variant".to_string(),
+                distribution: variants
+                    .iter()
+                    .map(|(v, count)| PropValueSnapshot {
+                        value: Some(v.to_string()),
+                        raw: v.to_string(),
+                        prop_type: "string".to_string(),
+                        count: *count,
+                    })
+                    .collect(),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_diff_snapshots() {
+        let base = ReportSnapshot {
+            components: vec![
+                component("Button", &["a.tsx", "a.tsx"], &[("primary", 2)]),
+                component("Legacy", &["a.tsx"], &[]),
+            ],
+        };
+        let head = ReportSnapshot {
+            components: vec![
+                component(
+                    "Button",
+                    &["a.tsx", "b.tsx", "b.tsx"],
+                    &[("primary", 2), ("danger", 1)],
+                ),
+                component("Dialog", &["c.tsx"], &[]),
+            ],
+        };
+
+        let diff = diff_snapshots(&base, &head);
+
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].name, "Dialog");
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].name, "Legacy");
+
+        assert_eq!(diff.changed.len(), 1);
+        let button = &diff.changed[0];
+        assert_eq!((button.base_count, button.head_count), (2, 3));
+        assert_eq!(button.props.len(), 1);
+        assert_eq!(button.props[0].status, ChangeStatus::Changed);
+        assert_eq!(button.props[0].values.len(), 1);
+        assert_eq!(button.props[0].values[0].value.as_deref(), Some("danger"));
+        assert_eq!(button.files.len(), 2);
+
+        let markdown = diff.to_markdown();
+        assert!(markdown.contains("### Added components (1)"));
+        assert!(markdown.contains("| `Button` | 2 | 3 | +1 |"));
+    }
+}
@@ -0,0 +1,407 @@
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use crate::AnalysisError;
+use crate::analyze::{ComponentSource, ComponentUsageAggregate};
+use crate::result::AnalysisReport;
+
+/// One row per component: name, source type, package, total count and distinct files
+///
+/// `total_count` and `distinct_files` cover the component's own usages; usages of wrapping
+/// components added by the roll-up are counted separately in `rolled_up_count`.
+pub fn components_csv(report: &AnalysisReport) -> String {
+    let mut rows = vec![csv_row(&[
+        "id",
+        "name",
+        "source_type",
+        "package",
+        "total_count",
+        "distinct_files",
+        "rolled_up_count",
+    ])];
+
+    for aggregate in report.components() {
+        let total_count = aggregate.usages().len();
+        let rolled_up_count = aggregate.statistics().total_count() - total_count;
+        rows.push(csv_row(&[
+            aggregate.id(),
+            aggregate.display_name(),
+            source_type(aggregate),
+            &package_label(aggregate),
+            &total_count.to_string(),
+            &distinct_files(aggregate).to_string(),
+            &rolled_up_count.to_string(),
+        ]));
+    }
+
+    rows.concat()
+}
+
+/// One row per usage with its location and props
+///
+/// Props are written as `key=raw` pairs separated by `; `.
+pub fn instances_csv(report: &AnalysisReport) -> String {
+    let mut rows = vec![csv_row(&["component", "file", "line", "col", "props"])];
+
+    for aggregate in report.components() {
+        for usage in aggregate.usages() {
+            let location = usage.occurrence().location();
+            let props = usage
+                .simplified_props()
+                .iter()
+                .map(|p| format!("{}={}", p.key(), p.raw()))
+                .collect::<Vec<_>>()
+                .join("; ");
+
+            rows.push(csv_row(&[
+                aggregate.display_name(),
+                &location.file().display_path(),
+                &location.span().start_line().to_string(),
+                &location.span().start_col().to_string(),
+                &props,
+            ]));
+        }
+    }
+
+    rows.concat()
+}
+
+/// One row per component, prop and distinct value
+pub fn prop_values_csv(report: &AnalysisReport) -> String {
+    let mut rows = vec![csv_row(&[
+        "component",
+        "prop",
+        "prop_type",
+        "value",
+        "raw",
+        "count",
+    ])];
+
+    for aggregate in report.components() {
+        for pattern in aggregate.statistics().prop_patterns() {
+            for distribution in pattern.distribution() {
+                rows.push(csv_row(&[
+                    aggregate.display_name(),
+                    pattern.key(),
+                    distribution.value_pattern(),
+                    distribution.value().unwrap_or_default(),
+                    distribution.raw(),
+                    &distribution.count().to_string(),
+                ]));
+            }
+        }
+    }
+
+    rows.concat()
+}
+
+/// Markdown summary with the `top` most used components and their prop value tables
+pub fn markdown_summary(report: &AnalysisReport, top: usize) -> String {
+    let mut components: Vec<&ComponentUsageAggregate> = report.components().iter().collect();
+    components.sort_by(|a, b| {
+        b.statistics()
+            .total_count()
+            .cmp(&a.statistics().total_count())
+            .then_with(|| a.display_name().cmp(b.display_name()))
+    });
+    components.truncate(top);
+
+    let mut md = String::from("# Component usage\n\n");
+    let total_usages: usize = report
+        .components()
+        .iter()
+        .map(|a| a.statistics().total_count())
+        .sum();
+    let _ = writeln!(
+        md,
+        "{} components, {} usages.\n",
+        report.components().len(),
+        total_usages
+    );
+
+    md.push_str("## Top components\n\n");
+    md.push_str("| Component | Source | Package | Usages | Files |\n");
+    md.push_str("| --- | --- | --- | ---: | ---: |\n");
+    for aggregate in &components {
+        let _ = writeln!(
+            md,
+            "| `{}` | {} | {} | {} | {} |",
+            escape_cell(aggregate.display_name()),
+            source_type(aggregate),
+            escape_cell(&package_label(aggregate)),
+            aggregate.statistics().total_count(),
+            distinct_files(aggregate)
+        );
+    }
+
+    for aggregate in &components {
+        let patterns = aggregate.statistics().prop_patterns();
+        if patterns.is_empty() {
+            continue;
+        }
+
+        let _ = writeln!(
+            md,
+            "\n## `{}` props\n",
+            escape_cell(aggregate.display_name())
+        );
+        md.push_str("| Prop | Type | Value | Count |\n| --- | --- | --- | ---: |\n");
+        for pattern in patterns {
+            let mut distribution: Vec<_> = pattern.distribution().iter().collect();
+            distribution.sort_by_key(|d| std::cmp::Reverse(d.count()));
+            for d in distribution {
+                let _ = writeln!(
+                    md,
+                    "| `{}` | {} | `{}` | {} |",
+                    escape_cell(pattern.key()),
+                    d.value_pattern(),
+                    escape_cell(d.value().unwrap_or(d.raw())),
+                    d.count()
+                );
+            }
+        }
+    }
+
+    md
+}
+
+const HTML_TEMPLATE: &str = include_str!("report.html");
+
+/// Single offline HTML file embedding the report JSON and a small viewer
+pub fn html_report(report: &AnalysisReport) -> crate::Result<String> {
+    let json = serde_json::to_string(report).map_err(|e| AnalysisError::new(e.to_string()))?;
+    // `<` only occurs inside JSON strings, so escaping it keeps `</script>` out of the document
+    let json = json.replace('<', "\\u003c");
+    Ok(HTML_TEMPLATE.replace("__CUIN_REPORT__", &json))
+}
+
+fn source_type(aggregate: &ComponentUsageAggregate) -> &'static str {
+    match aggregate.identity().source() {
+        ComponentSource::Internal { .. } => "internal",
+        ComponentSource::External { .. } => "external",
+        ComponentSource::Native => "native",
+    }
+}
+
+fn package_label(aggregate: &ComponentUsageAggregate) -> String {
+    let identity = aggregate.identity();
+    let package = match identity.source() {
+        ComponentSource::External { package } => Some(package),
+        ComponentSource::Internal { .. } => identity.package(),
+        ComponentSource::Native => None,
+    };
+    package
+        .map(|p| format!("{}@{}", p.name(), p.version()))
+        .unwrap_or_default()
+}
+
+fn distinct_files(aggregate: &ComponentUsageAggregate) -> usize {
+    aggregate
+        .usages()
+        .iter()
+        .map(|u| u.occurrence().location().file().display_path())
+        .collect::<BTreeSet<_>>()
+        .len()
+}
+
+fn csv_row(fields: &[&str]) -> String {
+    let mut row = fields
+        .iter()
+        .map(|field| csv_field(field))
+        .collect::<Vec<_>>()
+        .join(",");
+    row.push_str("\r\n");
+    row
+}
+
+/// Quote a field when it contains a delimiter, quote or line break (RFC 4180)
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Escape text for use inside a Markdown table cell
+pub(crate) fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AnalysisService;
+    use crate::testing::TestProject;
+
+    fn sample_report() -> AnalysisReport {
+        let project = TestProject::new(&[
+            (
+                "src/Button.tsx",
+                "export const Button = (props) => <button {...props} />;",
+            ),
+            (
+                "src/App.tsx",
+                r#"import { Button } from "./Button";
+
+export const App = () => (
+  <main>
+    <Button variant="primary" size={2} />
+    <Button variant="primary" label="Save, close" />
+  </main>
+);"#,
+            ),
+            (
+                "src/Form.tsx",
+                r#"import { Button } from "./Button";
+
+export const Form = () => <Button variant="danger" />;"#,
+            ),
+        ]);
+        let mut report = AnalysisService::default().run(project.root()).unwrap();
+        // Components and usages are collected in parallel, so fix their order
+        let components = report.components_mut();
+        components.sort_by(|a, b| a.display_name().cmp(b.display_name()));
+        for aggregate in components.iter_mut() {
+            aggregate.sort_usages();
+        }
+        report
+    }
+
+    #[test]
+    fn test_components_csv() {
+        assert_eq!(
+            components_csv(&sample_report()),
+            "id,name,source_type,package,total_count,distinct_files,rolled_up_count\r\n\
+             3bfd0c9e89fb21e3,Button,internal,app@1.0.0,3,2,0\r\n\
+             4b24e19a09122dc4,button,native,,1,1,0\r\n\
+             ebe4defc991cb07d,main,native,,1,1,0\r\n"
+        );
+    }
+
+    #[test]
+    fn test_components_csv_counts_rolled_up_usages_separately() {
+        let project = TestProject::new(&[
+            (
+                "src/Button.tsx",
+                "export const Button = (props) => <button {...props} />;",
+            ),
+            (
+                "src/FancyButton.tsx",
+                r#"import { memo } from "react";
+import { Button } from "./Button";
+export const FancyButton = memo(Button);"#,
+            ),
+            (
+                "src/App.tsx",
+                r#"import { Button } from "./Button";
+import { FancyButton } from "./FancyButton";
+export const App = () => <Button />;"#,
+            ),
+            (
+                "src/Form.tsx",
+                r#"import { FancyButton } from "./FancyButton";
+export const Form = () => (
+  <>
+    <FancyButton />
+    <FancyButton />
+  </>
+);"#,
+            ),
+        ]);
+        let config = crate::AnalyzerConfig {
+            include_native_elements: false,
+            roll_up_wrapped_usages: true,
+            ..crate::AnalyzerConfig::default()
+        };
+        let report = AnalysisService::new(config).run(project.root()).unwrap();
+
+        let csv = components_csv(&report);
+        let button = csv.lines().find(|line| line.contains(",Button,")).unwrap();
+        // One usage in one file, plus the two usages of `FancyButton`
+        assert!(button.ends_with(",1,1,2"), "{button}");
+    }
+
+    #[test]
+    fn test_instances_csv() {
+        assert_eq!(
+            instances_csv(&sample_report()),
+            "component,file,line,col,props\r\n\
+             Button,src/App.tsx,5,5,variant=primary; size=2\r\n\
+             Button,src/App.tsx,6,5,\"variant=primary; label=Save, close\"\r\n\
+             Button,src/Form.tsx,3,27,variant=danger\r\n\
+             button,src/Button.tsx,1,34,(spread)=props\r\n\
+             main,src/App.tsx,4,3,\"children=<Button variant=\"\"primary\"\" size={2} />\
+             <Button variant=\"\"primary\"\" label=\"\"Save, close\"\" />\"\r\n"
+        );
+    }
+
+    #[test]
+    fn test_prop_values_csv() {
+        assert_eq!(
+            prop_values_csv(&sample_report()),
+            "component,prop,prop_type,value,raw,count\r\n\
+             Button,label,string,\"Save, close\",\"Save, close\",1\r\n\
+             Button,size,literal,2,2,1\r\n\
+             Button,variant,string,danger,danger,1\r\n\
+             Button,variant,string,primary,primary,2\r\n\
+             button,(spread),spread,,props,1\r\n\
+             main,children,jsx,,\"<Button variant=\"\"primary\"\" size={2} />\
+             <Button variant=\"\"primary\"\" label=\"\"Save, close\"\" />\",1\r\n"
+        );
+    }
+
+    #[test]
+    fn test_markdown_summary() {
+        assert_eq!(
+            markdown_summary(&sample_report(), 2),
+            "# Component usage
+
+3 components, 5 usages.
+
+## Top components
+
+| Component | Source | Package | Usages | Files |
+| --- | --- | --- | ---: | ---: |
+| `Button` | internal | app@1.0.0 | 3 | 2 |
+| `button` | native |  | 1 | 1 |
+
+## `Button` props
+
+| Prop | Type | Value | Count |
+| --- | --- | --- | ---: |
+| `label` | string | `Save, close` | 1 |
+| `size` | literal | `2` | 1 |
+| `variant` | string | `primary` | 2 |
+| `variant` | string | `danger` | 1 |
+
+## `button` props
+
+| Prop | Type | Value | Count |
+| --- | --- | --- | ---: |
+| `(spread)` | spread | `props` | 1 |
+"
+        );
+    }
+
+    #[test]
+    fn test_csv_field_quoting() {
+        assert_eq!(csv_field("Button"), "Button");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_row(&["a", "b\nc"]), "a,\"b\nc\"\r\n");
+    }
+
+    #[test]
+    fn test_html_report_escapes_script_end() {
+        let report = AnalysisReport::new(
+            crate::result::AnalysisMetadata::new(std::path::PathBuf::from("/test/</script>")),
+            vec![],
+        );
+        let html = html_report(&report).unwrap();
+
+        assert!(!html.contains("__CUIN_REPORT__"));
+        assert!(html.contains("/test/\\u003c/script>"));
+        assert_eq!(html.matches("</script>").count(), 2);
+    }
+}
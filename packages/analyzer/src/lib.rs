@@ -1,15 +1,36 @@
 mod analyze;
+pub mod check;
 pub mod config;
+mod definition;
+pub mod diagnostic;
+pub mod diff;
+pub mod export;
+mod mdx;
 mod parser;
+pub mod query;
 mod resolver;
 pub mod result;
+pub mod rules;
+pub mod sarif;
+pub mod schema;
 pub mod service;
+mod sfc;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+pub mod stream;
+#[cfg(test)]
+mod testing;
 mod walk;
 
-pub use analyze::{Analyzer, ComponentUsage, ComponentUsageAggregate};
+pub use analyze::{Analyzer, ComponentUsage, ComponentUsageAggregate, FileAnalysis, IdOptions};
+pub use check::{Baseline, CheckOutcome, CheckPolicy, check};
 pub use config::AnalyzerConfig;
+pub use diff::{ReportDiff, ReportSnapshot, diff_reports, diff_snapshots};
+pub use query::Query;
 pub use result::{AnalysisReport, AnalysisResult};
+pub use rules::{Rule, RuleViolation};
 pub use service::AnalysisService;
+pub use stream::{StreamGranularity, aggregate_stream};
 
 use std::fmt;
 
@@ -49,17 +70,201 @@ pub type Result<T> = std::result::Result<T, AnalysisError>;
 #[cfg(feature = "napi")]
 use napi_derive::napi;
 
+/// Output formats accepted by `analyze`
+#[cfg(feature = "napi")]
+const FORMATS: [&str; 7] = [
+    "json",
+    "sarif",
+    "html",
+    "markdown",
+    "csv-components",
+    "csv-instances",
+    "csv-props",
+];
+
+/// Analyze the project and serialize the report
+///
+/// `format` is "json" (default), "sarif", "html", "markdown", "csv-components",
+/// "csv-instances" or "csv-props"; any other format is an error. `packages` lists dependency
+/// packages in `node_modules` to analyze as well, e.g. `@acme/*`.
 #[cfg(feature = "napi")]
 #[napi]
-pub fn analyze(input_path: String) -> std::result::Result<String, napi::Error> {
+pub fn analyze(
+    input_path: String,
+    rules: Option<String>,
+    format: Option<String>,
+    packages: Option<Vec<String>>,
+) -> std::result::Result<String, napi::Error> {
     use std::path::Path;
 
-    let config = AnalyzerConfig::default();
+    if let Some(format) = format.as_deref() {
+        if !FORMATS.contains(&format) {
+            return Err(napi::Error::from_reason(format!(
+                "Unknown format '{format}', expected one of: {}",
+                FORMATS.join(", ")
+            )));
+        }
+    }
+
+    let config = AnalyzerConfig {
+        rules: rules
+            .map(|json| Rule::parse_list(&json))
+            .transpose()
+            .map_err(|e| napi::Error::from_reason(e.message().to_string()))?
+            .unwrap_or_default(),
+        dependency_packages: packages.unwrap_or_default(),
+        ..AnalyzerConfig::default()
+    };
     let service = AnalysisService::new(config);
 
     let report = service
         .run(Path::new(&input_path))
         .map_err(|e| napi::Error::from_reason(e.message().to_string()))?;
 
-    serde_json::to_string_pretty(&report).map_err(|e| napi::Error::from_reason(e.to_string()))
+    let to_json = |e: serde_json::Error| napi::Error::from_reason(e.to_string());
+    match format.as_deref() {
+        Some("sarif") => serde_json::to_string_pretty(&sarif::to_sarif(&report)).map_err(to_json),
+        Some("html") => export::html_report(&report)
+            .map_err(|e| napi::Error::from_reason(e.message().to_string())),
+        Some("markdown") => Ok(export::markdown_summary(&report, 20)),
+        Some("csv-components") => Ok(export::components_csv(&report)),
+        Some("csv-instances") => Ok(export::instances_csv(&report)),
+        Some("csv-props") => Ok(export::prop_values_csv(&report)),
+        _ => serde_json::to_string_pretty(&report).map_err(to_json),
+    }
+}
+
+/// Diff two serialized reports, returning JSON or a Markdown summary when `format` is "markdown"
+///
+/// Formats other than "json" and "markdown" are an error.
+#[cfg(feature = "napi")]
+#[napi]
+pub fn diff(
+    base_report: String,
+    head_report: String,
+    format: Option<String>,
+) -> std::result::Result<String, napi::Error> {
+    let to_napi = |e: AnalysisError| napi::Error::from_reason(e.message().to_string());
+    let base = ReportSnapshot::from_json(&base_report).map_err(to_napi)?;
+    let head = ReportSnapshot::from_json(&head_report).map_err(to_napi)?;
+    let diff = diff_snapshots(&base, &head);
+
+    match format.as_deref() {
+        None | Some("json") => {
+            serde_json::to_string_pretty(&diff).map_err(|e| napi::Error::from_reason(e.to_string()))
+        }
+        Some("markdown") => Ok(diff.to_markdown()),
+        Some(other) => Err(napi::Error::from_reason(format!(
+            "Unknown format '{other}', expected one of: json, markdown"
+        ))),
+    }
+}
+
+/// Analyze the project and check it against the policy and baseline, returning the outcome JSON
+///
+/// Without a baseline every measured usage counts as new.
+#[cfg(feature = "napi")]
+#[napi(js_name = "check")]
+pub fn check_usage(
+    input_path: String,
+    policy: String,
+    baseline: Option<String>,
+) -> std::result::Result<String, napi::Error> {
+    use std::path::Path;
+
+    let to_napi = |e: AnalysisError| napi::Error::from_reason(e.message().to_string());
+    let policy = CheckPolicy::from_json(&policy).map_err(to_napi)?;
+    let baseline = baseline
+        .map(|json| Baseline::from_json(&json))
+        .transpose()
+        .map_err(to_napi)?
+        .unwrap_or_default();
+
+    let report = AnalysisService::new(AnalyzerConfig::default())
+        .run(Path::new(&input_path))
+        .map_err(to_napi)?;
+    let outcome = check(&report, &policy, &baseline);
+
+    serde_json::to_string_pretty(&outcome).map_err(|e| napi::Error::from_reason(e.to_string()))
+}
+
+/// Analyze the project and write NDJSON records to `output_path` as files finish
+///
+/// `granularity` is "usage" (default) or "file"; anything else is an error. Returns the
+/// metadata JSON.
+#[cfg(feature = "napi")]
+#[napi]
+pub fn analyze_stream(
+    input_path: String,
+    output_path: String,
+    granularity: Option<String>,
+) -> std::result::Result<String, napi::Error> {
+    use std::io::BufWriter;
+    use std::path::Path;
+
+    let to_napi = |e: AnalysisError| napi::Error::from_reason(e.message().to_string());
+    let granularity = match granularity.as_deref() {
+        None | Some("usage") => StreamGranularity::Usage,
+        Some("file") => StreamGranularity::File,
+        Some(other) => {
+            return Err(napi::Error::from_reason(format!(
+                "Unknown granularity '{other}', expected one of: usage, file"
+            )));
+        }
+    };
+    let file =
+        std::fs::File::create(&output_path).map_err(|e| napi::Error::from_reason(e.to_string()))?;
+
+    let metadata = AnalysisService::default()
+        .run_streaming(Path::new(&input_path), BufWriter::new(file), granularity)
+        .map_err(to_napi)?;
+
+    serde_json::to_string_pretty(&metadata).map_err(|e| napi::Error::from_reason(e.to_string()))
+}
+
+/// Aggregate an NDJSON stream file into per-component statistics
+#[cfg(feature = "napi")]
+#[napi]
+pub fn aggregate(stream_path: String) -> std::result::Result<String, napi::Error> {
+    use std::io::BufReader;
+
+    let file =
+        std::fs::File::open(&stream_path).map_err(|e| napi::Error::from_reason(e.to_string()))?;
+    let components = aggregate_stream(BufReader::new(file))
+        .map_err(|e| napi::Error::from_reason(e.message().to_string()))?;
+
+    serde_json::to_string_pretty(&components).map_err(|e| napi::Error::from_reason(e.to_string()))
+}
+
+/// Analyze the project and return the components JSON with only the usages matching `query`
+#[cfg(feature = "napi")]
+#[napi]
+pub fn query(input_path: String, query: String) -> std::result::Result<String, napi::Error> {
+    use std::path::Path;
+
+    let to_napi = |e: AnalysisError| napi::Error::from_reason(e.message().to_string());
+    let query = Query::parse(&query).map_err(to_napi)?;
+    let report = AnalysisService::default()
+        .run(Path::new(&input_path))
+        .map_err(to_napi)?;
+
+    serde_json::to_string_pretty(&query.filter(report.components()))
+        .map_err(|e| napi::Error::from_reason(e.to_string()))
+}
+
+/// Analyze the project and write the report into a SQLite database at `output_path`
+#[cfg(all(feature = "napi", feature = "sqlite"))]
+#[napi]
+pub fn export_sqlite(
+    input_path: String,
+    output_path: String,
+) -> std::result::Result<(), napi::Error> {
+    use std::path::Path;
+
+    let to_napi = |e: AnalysisError| napi::Error::from_reason(e.message().to_string());
+    let report = AnalysisService::default()
+        .run(Path::new(&input_path))
+        .map_err(to_napi)?;
+
+    sqlite::write_sqlite(&report, Path::new(&output_path)).map_err(to_napi)
 }
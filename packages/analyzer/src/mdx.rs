@@ -0,0 +1,332 @@
+//! MDX front-end
+//!
+//! MDX documents mix Markdown with ESM (`import`/`export` blocks), JSX and `{expression}`
+//! blocks. [`scan`] finds those blocks without parsing the Markdown, and [`mask`] blanks out
+//! everything else so a block can be parsed as JavaScript at its original offsets.
+
+use std::ops::Range;
+
+/// Byte ranges of the code blocks of an MDX document
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct MdxBlocks {
+    /// `import` and `export` blocks
+    pub esm: Vec<Range<usize>>,
+    /// JSX elements and `{expression}` blocks, parsed one at a time
+    pub jsx: Vec<Range<usize>>,
+}
+
+/// Find the ESM, JSX and expression blocks of an MDX document
+///
+/// Frontmatter, fenced code blocks, inline code and escaped characters are skipped.
+pub(crate) fn scan(source: &str) -> MdxBlocks {
+    let bytes = source.as_bytes();
+    let mut blocks = MdxBlocks::default();
+    let mut pos = skip_frontmatter(source);
+
+    while pos < bytes.len() {
+        let at_line_start = pos == 0 || bytes[pos - 1] == b'\n';
+        if at_line_start {
+            let line = &source[pos..line_end(source, pos)];
+            let trimmed = line.trim_start_matches(' ');
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                pos = skip_fence(source, pos, trimmed);
+                continue;
+            }
+            if is_esm_line(line) {
+                let end = esm_block_end(source, pos);
+                blocks.esm.push(pos..end);
+                pos = end;
+                continue;
+            }
+        }
+
+        match bytes[pos] {
+            b'\\' => pos += 2,
+            b'`' => pos = skip_code_span(source, pos),
+            b'{' => match skip_braces(source, pos) {
+                Some(end) => {
+                    blocks.jsx.push(pos..end);
+                    pos = end;
+                }
+                None => pos += 1,
+            },
+            b'<' if starts_tag(source, pos) => match element_end(source, pos) {
+                Some(end) => {
+                    blocks.jsx.push(pos..end);
+                    pos = end;
+                }
+                None => pos += 1,
+            },
+            _ => pos += 1,
+        }
+    }
+
+    blocks
+}
+
+/// Replace everything outside `keep` with spaces, keeping line breaks and byte offsets
+pub(crate) fn mask(source: &str, keep: &[Range<usize>]) -> String {
+    let mut masked = String::with_capacity(source.len());
+    for (i, c) in source.char_indices() {
+        if c == '\n' || c == '\r' || keep.iter().any(|range| range.contains(&i)) {
+            masked.push(c);
+        } else {
+            masked.extend(std::iter::repeat_n(' ', c.len_utf8()));
+        }
+    }
+    masked
+}
+
+fn line_end(source: &str, pos: usize) -> usize {
+    source[pos..].find('\n').map_or(source.len(), |i| pos + i)
+}
+
+/// Offset after the closing `---` of a leading YAML frontmatter, or 0
+fn skip_frontmatter(source: &str) -> usize {
+    if !source.starts_with("---\n") && !source.starts_with("---\r\n") {
+        return 0;
+    }
+    let mut pos = line_end(source, 0) + 1;
+    while pos < source.len() {
+        let end = line_end(source, pos);
+        if source[pos..end].trim_end() == "---" {
+            return (end + 1).min(source.len());
+        }
+        pos = end + 1;
+    }
+    0
+}
+
+/// Offset after the closing fence of a fenced code block opening at `pos`
+fn skip_fence(source: &str, pos: usize, opening: &str) -> usize {
+    let marker = opening.as_bytes()[0] as char;
+    let length = opening.len() - opening.trim_start_matches(marker).len();
+
+    let mut line = line_end(source, pos) + 1;
+    while line < source.len() {
+        let end = line_end(source, line);
+        let trimmed = source[line..end].trim();
+        let run = trimmed.len() - trimmed.trim_start_matches(marker).len();
+        if run >= length && trimmed[run..].is_empty() {
+            return end;
+        }
+        line = end + 1;
+    }
+    source.len()
+}
+
+fn is_esm_line(line: &str) -> bool {
+    ["import", "export"].iter().any(|keyword| {
+        line.strip_prefix(keyword)
+            .is_some_and(|rest| rest.starts_with([' ', '{', '*', '\t']))
+    })
+}
+
+/// An ESM block ends at the first blank line outside of braces
+fn esm_block_end(source: &str, start: usize) -> usize {
+    let bytes = source.as_bytes();
+    let mut pos = start;
+    while pos < bytes.len() {
+        match bytes[pos] {
+            b'{' => pos = skip_braces(source, pos).unwrap_or(source.len()),
+            quote @ (b'"' | b'\'' | b'`') => pos = skip_string(source, pos, quote),
+            b'\n' => {
+                let next = line_end(source, pos + 1);
+                if source[pos + 1..next].trim().is_empty() {
+                    return pos;
+                }
+                pos += 1;
+            }
+            _ => pos += 1,
+        }
+    }
+    source.len()
+}
+
+/// Offset after an inline code span, or after the backticks when it is not closed
+fn skip_code_span(source: &str, pos: usize) -> usize {
+    let ticks = source[pos..].len() - source[pos..].trim_start_matches('`').len();
+    let after = pos + ticks;
+    let fence = &source[pos..after];
+
+    let mut search = after;
+    while let Some(i) = source[search..].find(fence) {
+        let start = search + i;
+        let run = source[start..].len() - source[start..].trim_start_matches('`').len();
+        if run == ticks {
+            return start + ticks;
+        }
+        search = start + run;
+    }
+    after
+}
+
+/// Offset after the string starting with `quote` at `pos`
+///
+/// Single and double quoted strings end at a line break when they are not terminated.
+pub(crate) fn skip_string(source: &str, pos: usize, quote: u8) -> usize {
+    let bytes = source.as_bytes();
+    let mut i = pos + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'\n' if quote != b'`' => return i,
+            c if c == quote => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// Offset after the `}` matching the `{` at `pos`
+///
+/// Strings, comments and JSX elements inside the braces are skipped, so apostrophes in JSX
+/// text do not open a string.
+pub(crate) fn skip_braces(source: &str, pos: usize) -> Option<usize> {
+    let bytes = source.as_bytes();
+    let mut depth = 0;
+    let mut i = pos;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'{' => {
+                depth += 1;
+                i += 1;
+            }
+            b'}' => {
+                depth -= 1;
+                i += 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            quote @ (b'"' | b'\'' | b'`') => i = skip_string(source, i, quote),
+            b'/' if bytes.get(i + 1) == Some(&b'/') => i = line_end(source, i),
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = source[i + 2..]
+                    .find("*/")
+                    .map_or(bytes.len(), |end| i + 2 + end + 2);
+            }
+            b'<' if starts_tag(source, i) => i = element_end(source, i)?,
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+/// Whether `<` at `pos` opens a tag: `<Name`, `<>`, `</` (autolinks like `<https://…>` do not)
+fn starts_tag(source: &str, pos: usize) -> bool {
+    let rest = &source[pos + 1..];
+    match rest.chars().next() {
+        Some('>' | '/') => true,
+        Some(c) if c.is_ascii_alphabetic() => {
+            let name_end = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || "_$.-:".contains(c)))
+                .unwrap_or(rest.len());
+            !(rest[..name_end].contains(':') && rest[name_end..].starts_with('/'))
+        }
+        _ => false,
+    }
+}
+
+/// Offset after the tag starting at `pos`, and whether it is self-closing
+fn tag_end(source: &str, pos: usize) -> Option<(usize, bool)> {
+    let bytes = source.as_bytes();
+    let mut i = pos + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'{' => i = skip_braces(source, i)?,
+            quote @ (b'"' | b'\'') => i = skip_string(source, i, quote),
+            b'>' => return Some((i + 1, bytes[i - 1] == b'/')),
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+/// Offset after the element starting at `pos`, including its closing tag
+///
+/// Returns `None` when the element is never closed.
+fn element_end(source: &str, pos: usize) -> Option<usize> {
+    let bytes = source.as_bytes();
+    let mut depth = 0usize;
+    let mut i = pos;
+    loop {
+        let closing = bytes.get(i + 1) == Some(&b'/');
+        let (end, self_closing) = tag_end(source, i)?;
+        if closing {
+            depth = depth.checked_sub(1)?;
+        } else if !self_closing {
+            depth += 1;
+        }
+        i = end;
+        if depth == 0 {
+            return Some(i);
+        }
+
+        // Move to the next tag, skipping expressions and inline code in the children
+        loop {
+            match bytes.get(i)? {
+                b'{' => i = skip_braces(source, i)?,
+                b'`' => i = skip_code_span(source, i),
+                b'<' if starts_tag(source, i) => break,
+                _ => i += 1,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_mdx_blocks() {
+        let source = r#"---
+title: <Not a component>
+---
+import { Button } from "ui";
+export const meta = {
+  title: "Docs",
+
+  draft: false,
+};
+
+# Hello <Badge>new</Badge>
+
+Press `<Kbd />` or <Kbd>K</Kbd>, don't {props.count} <https://example.com>
+
+```jsx
+<Button>In a code block</Button>
+```
+
+<Callout type="info">
+  It's **Markdown** with a {`}`} brace.
+
+  <Button variant="primary" onClick={() => alert("<hi>")} />
+</Callout>
+"#;
+
+        let blocks = scan(source);
+        let text = |range: &Range<usize>| &source[range.clone()];
+
+        // Consecutive ESM lines form one block, blank lines inside braces do not end it
+        assert_eq!(blocks.esm.len(), 1);
+        assert!(text(&blocks.esm[0]).starts_with(r#"import { Button } from "ui";"#));
+        assert!(text(&blocks.esm[0]).ends_with("draft: false,\n};"));
+
+        let jsx: Vec<&str> = blocks.jsx.iter().map(text).collect();
+        assert_eq!(jsx.len(), 4);
+        assert_eq!(jsx[0], "<Badge>new</Badge>");
+        assert_eq!(jsx[1], "<Kbd>K</Kbd>");
+        assert_eq!(jsx[2], "{props.count}");
+        assert!(jsx[3].starts_with("<Callout") && jsx[3].ends_with("</Callout>"));
+    }
+
+    #[test]
+    fn test_mask_keeps_offsets() {
+        let source = "é <A />\n<B />";
+        let masked = mask(source, std::slice::from_ref(&(3..8)));
+        assert_eq!(masked.len(), source.len());
+        assert_eq!(masked, "   <A />\n     ");
+    }
+}
@@ -1,43 +1,221 @@
 use oxc::ast::ast::{
     JSXAttributeItem, JSXAttributeName, JSXAttributeValue, JSXChild, JSXElementName, JSXExpression,
-    JSXOpeningElement, JSXText,
+    JSXMemberExpression, JSXMemberExpressionObject, JSXOpeningElement, JSXText,
 };
 use oxc::{allocator::Allocator, parser::Parser, span::SourceType};
 use oxc_ast_visit::Visit;
 use oxc_syntax::module_record::{ImportImportName, ModuleRecord};
+use std::collections::HashSet;
 
 use crate::AnalysisError;
 use crate::analyze::{
     ChildNode, ExpressionKind, JSXAttribute, JSXElementOccurrence, JSXElementReference,
-    PropAssignment, PropName, PropValue, ResolvedProp, SpreadAttribute,
+    PropAssignment, PropName, PropValue, RenderContext, ResolvedProp, SpreadAttribute,
+};
+use crate::diagnostic::Diagnostic;
+use crate::mdx;
+use crate::sfc::{
+    self, AttributeValue, SfcBlocks, Template, TemplateAttribute, TemplateChild, TemplateElement,
+    TemplateSyntax,
 };
 // SourceFile, SourceLocation, Span, ImportBinding, ImportedName, ModuleSpecifier are defined in this file
 
-pub struct OxcParser;
+#[derive(Debug, Clone, Copy)]
+pub struct OxcParser {
+    /// Collect `createElement` and `jsx()` calls as elements
+    create_element_calls: bool,
+}
 
 impl OxcParser {
     pub fn new() -> Self {
-        Self
+        Self {
+            create_element_calls: true,
+        }
+    }
+
+    pub fn with_create_element_calls(mut self, enabled: bool) -> Self {
+        self.create_element_calls = enabled;
+        self
     }
 
+    /// Parse a file for JSX elements, imports and syntax errors
+    ///
+    /// Files without `<` (nor `createElement`/`jsx` text when those calls are collected) cannot
+    /// hold elements and are skipped without parsing, so their syntax errors are not reported.
     pub fn parse(&self, source_text: &str, file: &SourceFile) -> Result<ParsedFile, AnalysisError> {
-        if !source_text.contains('<') {
-            return Ok(ParsedFile::new(Vec::new(), Vec::new()));
+        match file.canonical().extension().and_then(|ext| ext.to_str()) {
+            Some("mdx") => return Ok(self.parse_mdx(source_text, file)),
+            Some("astro") => {
+                let blocks = sfc::astro_blocks(source_text);
+                return Ok(self.parse_sfc(source_text, file, blocks, TemplateSyntax::Astro));
+            }
+            Some("vue") => {
+                let blocks = sfc::vue_blocks(source_text);
+                return Ok(self.parse_sfc(source_text, file, blocks, TemplateSyntax::Vue));
+            }
+            _ => {}
+        }
+
+        let may_call_create_element = self.create_element_calls
+            && (source_text.contains("createElement") || source_text.contains("jsx"));
+        if !source_text.contains('<') && !may_call_create_element {
+            return Ok(ParsedFile::new(Vec::new(), Vec::new(), Vec::new()));
         }
 
         let source_type = SourceType::from_path(file.canonical())
             .unwrap_or_default()
             .with_jsx(true);
 
+        let lines = LineIndex::new(source_text);
+        Ok(self.parse_program(source_text, 0, &lines, file, source_type))
+    }
+
+    /// Parse the ESM, JSX and expression blocks of an MDX document
+    ///
+    /// The ESM blocks are parsed together with the rest of the document blanked out. Each JSX
+    /// block is parsed on its own slice, with spans shifted back to the document.
+    fn parse_mdx(&self, source_text: &str, file: &SourceFile) -> ParsedFile {
+        let blocks = mdx::scan(source_text);
+        let source_type = SourceType::jsx();
+        let lines = LineIndex::new(source_text);
+
+        let esm = mdx::mask(source_text, &blocks.esm);
+        let mut parsed = self.parse_program(&esm, 0, &lines, file, source_type);
+        for block in &blocks.jsx {
+            let code = &source_text[block.clone()];
+            parsed.extend(self.parse_program(code, block.start, &lines, file, source_type));
+        }
+        parsed
+            .jsx_elements
+            .sort_by_key(|element| element.location().span().start());
+        parsed
+    }
+
+    /// Parse the script blocks and the template of an Astro or Vue component
+    ///
+    /// Template elements are matched against the imports of the scripts like JSX elements.
+    /// Expressions in Astro templates may hold JSX and are parsed for elements as well.
+    fn parse_sfc(
+        &self,
+        source_text: &str,
+        file: &SourceFile,
+        blocks: SfcBlocks,
+        syntax: TemplateSyntax,
+    ) -> ParsedFile {
+        let script_type = SourceType::from_extension(&blocks.lang).unwrap_or_default();
+        let lines = LineIndex::new(source_text);
+        let script = mdx::mask(source_text, &blocks.scripts);
+        let mut parsed = self.parse_program(&script, 0, &lines, file, script_type);
+
+        let Some(range) = blocks.template else {
+            return parsed;
+        };
+        let template = sfc::parse_template(source_text, range, syntax);
+        let expression_type = match syntax {
+            TemplateSyntax::Astro => SourceType::tsx(),
+            TemplateSyntax::Vue => SourceType::ts(),
+        };
+        // Aliases declared in script functions are not visible to the template
+        let module_aliases = parsed
+            .aliases
+            .iter()
+            .filter(|alias| alias.scope.is_none())
+            .map(|alias| &alias.binding);
+        let bindings: HashSet<&str> = parsed
+            .imports
+            .iter()
+            .chain(module_aliases)
+            .map(|binding| binding.local_name())
+            .collect();
+
+        let converter = TemplateConverter {
+            source_text,
+            source_file: file,
+            lines: &lines,
+            template: &template,
+            syntax,
+            expression_type,
+            bindings,
+        };
+        let elements: Vec<JSXElementOccurrence> = template
+            .elements
+            .iter()
+            .zip(converter.contexts())
+            // `<template>` only groups elements in Vue
+            .filter(|(element, _)| syntax != TemplateSyntax::Vue || element.name != "template")
+            .map(|(element, context)| converter.occurrence(element).with_context(context))
+            .collect();
+        parsed.jsx_elements.extend(elements);
+
+        if syntax == TemplateSyntax::Astro {
+            for expression in &template.expressions {
+                let code = &source_text[expression.clone()];
+                parsed.extend(self.parse_program(
+                    code,
+                    expression.start,
+                    &lines,
+                    file,
+                    expression_type,
+                ));
+            }
+        }
+
+        // The file is the component, unless an element is rendered in a named one
+        let component = file.canonical().file_stem().and_then(|stem| stem.to_str());
+        for element in &mut parsed.jsx_elements {
+            let context = element.context_mut();
+            if context.component.is_none() {
+                context.component = component.map(str::to_string);
+            }
+        }
+        parsed
+            .jsx_elements
+            .sort_by_key(|element| element.location().span().start());
+        parsed
+    }
+
+    /// Parse `code` and collect its elements and bindings
+    ///
+    /// `code` is a part of the document starting at `offset`, or the document with parts
+    /// blanked out; spans are shifted by `offset` to point into the document.
+    fn parse_program(
+        &self,
+        code: &str,
+        offset: usize,
+        lines: &LineIndex,
+        file: &SourceFile,
+        source_type: SourceType,
+    ) -> ParsedFile {
         let allocator = Allocator::default();
-        let ret = Parser::new(&allocator, source_text, source_type).parse();
+        let ret = Parser::new(&allocator, code, source_type).parse();
 
-        let mut collector = JSXCollector::new(source_text, file);
+        let offset = offset as u32;
+        let mut collector = JSXCollector::new(code, file, lines, offset);
+        collector.create_element_calls = self.create_element_calls;
+        collector.imports = extract_imports(&ret.module_record);
         collector.visit_program(&ret.program);
 
-        let imports = extract_imports(&ret.module_record);
+        let mut imports = collector.imports;
+        imports.extend(collector.dynamic_imports);
+        let aliases = resolve_aliases(&imports, &collector.aliases);
+
+        let diagnostics = ret
+            .errors
+            .iter()
+            .map(|error| {
+                let span = error
+                    .labels
+                    .as_ref()
+                    .and_then(|labels| labels.first())
+                    .map(|label| {
+                        let start = offset + label.offset() as u32;
+                        lines.span(start, start + label.len() as u32)
+                    });
+                Diagnostic::parse_error(file, error.message.to_string(), span)
+            })
+            .collect();
 
-        Ok(ParsedFile::new(collector.elements, imports))
+        ParsedFile::new(collector.elements, imports, aliases).with_diagnostics(diagnostics)
     }
 }
 
@@ -51,17 +229,60 @@ impl Default for OxcParser {
 pub struct ParsedFile {
     jsx_elements: Vec<JSXElementOccurrence>,
     imports: Vec<ImportBinding>,
+    /// Local variables derived from imports (`const { Button } = UI`, `const Btn = UI.Button`)
+    aliases: Vec<AliasBinding>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// Binding of a local alias and the document range of the function declaring it
+#[derive(Debug, Clone)]
+struct AliasBinding {
+    binding: ImportBinding,
+    /// `None` at module level
+    scope: Option<std::ops::Range<u32>>,
+}
+
+impl AliasBinding {
+    fn is_visible_at(&self, offset: u32) -> bool {
+        self.scope
+            .as_ref()
+            .is_none_or(|scope| scope.contains(&offset))
+    }
 }
 
 impl ParsedFile {
     /// Create a new ParsedFile
-    pub fn new(jsx_elements: Vec<JSXElementOccurrence>, imports: Vec<ImportBinding>) -> Self {
+    fn new(
+        jsx_elements: Vec<JSXElementOccurrence>,
+        imports: Vec<ImportBinding>,
+        aliases: Vec<AliasBinding>,
+    ) -> Self {
         Self {
             jsx_elements,
             imports,
+            aliases,
+            diagnostics: Vec::new(),
         }
     }
 
+    pub fn with_diagnostics(mut self, diagnostics: Vec<Diagnostic>) -> Self {
+        self.diagnostics = diagnostics;
+        self
+    }
+
+    /// Add the elements, bindings and diagnostics of another part of the same file
+    fn extend(&mut self, other: ParsedFile) {
+        self.jsx_elements.extend(other.jsx_elements);
+        self.imports.extend(other.imports);
+        self.aliases.extend(other.aliases);
+        self.diagnostics.extend(other.diagnostics);
+    }
+
+    /// Problems found while parsing, such as syntax errors
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
     /// Get the list of JSX elements
     pub fn jsx_elements(&self) -> &[JSXElementOccurrence] {
         &self.jsx_elements
@@ -72,36 +293,138 @@ impl ParsedFile {
         &self.imports
     }
 
+    #[cfg(test)]
+    fn aliases(&self) -> impl Iterator<Item = &ImportBinding> {
+        self.aliases.iter().map(|alias| &alias.binding)
+    }
+
     /// Find the ImportBinding corresponding to a JSX element
+    ///
+    /// Falls back to the aliases declared in a function enclosing the element (innermost first)
+    /// or at module level when the identifier is not imported directly.
     pub fn find_binding_for_element(
         &self,
         element: &JSXElementOccurrence,
     ) -> Option<&ImportBinding> {
         let identifier = element.tag_name().get_identifier();
-        self.imports
+        if let Some(binding) = self
+            .imports
             .iter()
             .find(|binding| binding.local_name() == identifier)
+        {
+            return Some(binding);
+        }
+
+        let start = element.location().span().start();
+        self.aliases
+            .iter()
+            .filter(|alias| alias.binding.local_name() == identifier && alias.is_visible_at(start))
+            .min_by_key(|alias| {
+                alias
+                    .scope
+                    .as_ref()
+                    .map_or(u32::MAX, |scope| scope.len() as u32)
+            })
+            .map(|alias| &alias.binding)
     }
 }
 
 struct JSXCollector<'b> {
     elements: Vec<JSXElementOccurrence>,
+    /// Code that was parsed, which spans and raw text refer to
     source_text: &'b str,
     source_file: &'b SourceFile,
+    /// Lines of the whole document
+    lines: &'b LineIndex<'b>,
+    /// Offset of `source_text` in the document
+    offset: u32,
     variables: std::collections::HashMap<String, VariableValue>,
+    /// Bindings of the `import` declarations
+    imports: Vec<ImportBinding>,
+    /// Bindings created by `require()` or lazy `import()` loaders
+    dynamic_imports: Vec<ImportBinding>,
+    /// Variables assigned from other identifiers (resolved against imports after visiting)
+    aliases: Vec<LocalAlias>,
+    /// Collect `createElement` and `jsx()` calls as elements
+    create_element_calls: bool,
+    /// Nesting of the code being visited
+    scope: RenderScope,
 }
 
 impl<'b> JSXCollector<'b> {
-    fn new(source_text: &'b str, source_file: &'b SourceFile) -> Self {
+    fn new(
+        source_text: &'b str,
+        source_file: &'b SourceFile,
+        lines: &'b LineIndex<'b>,
+        offset: u32,
+    ) -> Self {
         Self {
             elements: Vec::new(),
             source_text,
             source_file,
+            lines,
+            offset,
             variables: std::collections::HashMap::new(),
+            imports: Vec::new(),
+            dynamic_imports: Vec::new(),
+            aliases: Vec::new(),
+            create_element_calls: false,
+            scope: RenderScope::default(),
         }
     }
 }
 
+/// Loops, conditions and functions enclosing the code being visited
+#[derive(Debug, Default)]
+struct RenderScope {
+    loops: usize,
+    conditionals: usize,
+    render_props: usize,
+    props: usize,
+    /// Names of the enclosing functions and classes, innermost last
+    functions: Vec<Option<String>>,
+    /// Document ranges of the enclosing functions, innermost last
+    function_ranges: Vec<std::ops::Range<u32>>,
+    /// Variable being initialized, which names an anonymous function
+    declarator: Option<String>,
+}
+
+impl RenderScope {
+    fn context(&self) -> RenderContext {
+        RenderContext {
+            in_loop: self.loops > 0,
+            conditional: self.conditionals > 0,
+            in_render_prop: self.render_props > 0,
+            in_prop: self.props > 0,
+            component: self
+                .functions
+                .iter()
+                .rev()
+                .flatten()
+                .find(|name| is_component_name(name))
+                .cloned(),
+        }
+    }
+}
+
+/// Components are named in PascalCase
+fn is_component_name(name: &str) -> bool {
+    name.chars().next().is_some_and(|c| c.is_ascii_uppercase())
+}
+
+/// Variable that refers to another identifier, optionally through member access
+///
+/// `const { Button } = UI` is recorded as `Button -> UI` with members `["Button"]`. Only
+/// function scopes are tracked: an alias declared in a block applies to its whole function.
+#[derive(Debug, Clone)]
+struct LocalAlias {
+    local_name: String,
+    target: String,
+    members: Vec<String>,
+    /// Document range of the function declaring the alias, `None` at module level
+    scope: Option<std::ops::Range<u32>>,
+}
+
 /// Information about a value stored in a variable
 #[derive(Debug, Clone)]
 enum VariableValue {
@@ -118,6 +441,14 @@ struct ObjectProperty {
     value_pattern: String,
     value: Option<String>,
     raw: String,
+    type_annotation: Option<String>,
+}
+
+impl ObjectProperty {
+    fn into_resolved(self) -> ResolvedProp {
+        ResolvedProp::new(self.key, self.value_pattern, self.value, self.raw)
+            .with_type_annotation(self.type_annotation)
+    }
 }
 
 impl<'a, 'b> Visit<'a> for JSXCollector<'b> {
@@ -130,30 +461,746 @@ impl<'a, 'b> Visit<'a> for JSXCollector<'b> {
 
             // Process only if there is an initialization expression
             if let Some(init) = &it.init {
-                let value = analyze_variable_value(init);
+                let value = analyze_variable_value(init, self.source_text);
                 self.variables.insert(var_name, value);
             }
         }
 
+        // const { Button } = require("ui"), const Modal = lazy(() => import("./Modal")), ...
+        if let Some(init) = &it.init {
+            let bindings =
+                extract_dynamic_bindings(&it.id.kind, init, |callee| self.is_lazy_loader(callee));
+            self.dynamic_imports.extend(bindings);
+            let scope = self.scope.function_ranges.last().cloned();
+            self.aliases
+                .extend(extract_aliases(&it.id.kind, init, scope));
+        }
+
+        // `const Card = () => ...` names the function it is initialized with
+        self.scope.declarator = match &it.id.kind {
+            BindingPatternKind::BindingIdentifier(id) if it.init.is_some() => {
+                Some(id.name.to_string())
+            }
+            _ => None,
+        };
+
         // Continue default visiting behavior (visit child nodes as well)
         oxc_ast_visit::walk::walk_variable_declarator(self, it);
+        self.scope.declarator = None;
+    }
+
+    fn visit_function(
+        &mut self,
+        it: &oxc::ast::ast::Function<'a>,
+        flags: oxc_syntax::scope::ScopeFlags,
+    ) {
+        let name = it.id.as_ref().map(|id| id.name.to_string());
+        let declarator = self.scope.declarator.take();
+        self.scope.functions.push(name.or(declarator));
+        self.scope.function_ranges.push(self.range(it.span));
+        oxc_ast_visit::walk::walk_function(self, it, flags);
+        self.scope.function_ranges.pop();
+        self.scope.functions.pop();
+    }
+
+    fn visit_arrow_function_expression(&mut self, it: &oxc::ast::ast::ArrowFunctionExpression<'a>) {
+        let declarator = self.scope.declarator.take();
+        self.scope.functions.push(declarator);
+        self.scope.function_ranges.push(self.range(it.span));
+        oxc_ast_visit::walk::walk_arrow_function_expression(self, it);
+        self.scope.function_ranges.pop();
+        self.scope.functions.pop();
+    }
+
+    fn visit_class(&mut self, it: &oxc::ast::ast::Class<'a>) {
+        let name = it.id.as_ref().map(|id| id.name.to_string());
+        let declarator = self.scope.declarator.take();
+        self.scope.functions.push(name.or(declarator));
+        oxc_ast_visit::walk::walk_class(self, it);
+        self.scope.functions.pop();
+    }
+
+    fn visit_logical_expression(&mut self, it: &oxc::ast::ast::LogicalExpression<'a>) {
+        // `show && <Modal />`, `title ?? <Placeholder />`
+        self.visit_expression(&it.left);
+        self.nested(conditionals_of, |v| v.visit_expression(&it.right));
+    }
+
+    fn visit_conditional_expression(&mut self, it: &oxc::ast::ast::ConditionalExpression<'a>) {
+        self.visit_expression(&it.test);
+        self.nested(conditionals_of, |v| {
+            v.visit_expression(&it.consequent);
+            v.visit_expression(&it.alternate);
+        });
+    }
+
+    fn visit_jsx_attribute(&mut self, it: &oxc::ast::ast::JSXAttribute<'a>) {
+        let is_render_prop = matches!(
+            &it.value,
+            Some(JSXAttributeValue::ExpressionContainer(container))
+                if container.expression.as_expression().is_some_and(is_function)
+        );
+        let counter = if is_render_prop {
+            render_props_of
+        } else {
+            props_of
+        };
+        self.nested(counter, |v| oxc_ast_visit::walk::walk_jsx_attribute(v, it));
+    }
+
+    fn visit_jsx_child(&mut self, it: &JSXChild<'a>) {
+        // `<List>{(item) => <Row item={item} />}</List>`
+        match it {
+            JSXChild::ExpressionContainer(container)
+                if container
+                    .expression
+                    .as_expression()
+                    .is_some_and(is_function) =>
+            {
+                self.nested(render_props_of, |v| {
+                    oxc_ast_visit::walk::walk_jsx_child(v, it)
+                });
+            }
+            _ => oxc_ast_visit::walk::walk_jsx_child(self, it),
+        }
     }
 
     fn visit_jsx_element(&mut self, it: &oxc::ast::ast::JSXElement<'a>) {
         let tag_name = format_component_name(&it.opening_element.name);
-        let attributes = extract_props(&it.opening_element, &self.variables);
-        let span = create_span_with_position(self.source_text, it.span.start, it.span.end);
-        let location = SourceLocation::new(self.source_file.clone(), span);
+        let attributes = extract_props(&it.opening_element, &self.variables, self.source_text);
+        let location = self.location(it.span);
         let raw_text = normalize_indentation(it.span.source_text(self.source_text));
         let children = extract_children(&it.children, self.source_text);
 
-        let element = JSXElementOccurrence::new(location, tag_name, attributes, raw_text, children);
+        let element = JSXElementOccurrence::new(location, tag_name, attributes, raw_text, children)
+            .with_context(self.scope.context());
         self.elements.push(element);
 
         // Recursively visit child JSX elements as well
         // This allows nested JSX elements to be collected individually
         oxc_ast_visit::walk::walk_jsx_element(self, it);
     }
+
+    fn visit_call_expression(&mut self, it: &oxc::ast::ast::CallExpression<'a>) {
+        if self.create_element_calls {
+            if let Some(element) = self.create_element_occurrence(it) {
+                self.elements.push(element);
+                // Nested calls in props and children are collected individually
+                self.visit_create_element_arguments(it);
+                return;
+            }
+        }
+
+        // `items.map((item) => <Row item={item} />)`
+        if is_map_call(&it.callee) {
+            self.visit_expression(&it.callee);
+            self.nested(loops_of, |v| v.visit_arguments(&it.arguments));
+            return;
+        }
+
+        oxc_ast_visit::walk::walk_call_expression(self, it);
+    }
+}
+
+impl JSXCollector<'_> {
+    /// Build an element from `React.createElement(Button, props, ...children)` or
+    /// `_jsx(Button, props)`
+    fn create_element_occurrence(
+        &self,
+        call: &oxc::ast::ast::CallExpression,
+    ) -> Option<JSXElementOccurrence> {
+        let call_kind = self.create_element_call_kind(&call.callee)?;
+        let tag_name = create_element_type(call.arguments.first()?.as_expression()?)?;
+
+        let mut attributes = Vec::new();
+        let mut children = Vec::new();
+        if let Some(props) = call.arguments.get(1).and_then(|arg| arg.as_expression()) {
+            self.extract_call_props(props, &mut attributes, &mut children);
+        }
+        // The jsx runtime passes children as a prop and the key as the third argument
+        if call_kind == CreateElementCall::CreateElement {
+            children.extend(
+                call.arguments
+                    .iter()
+                    .skip(2)
+                    .filter_map(|arg| arg.as_expression())
+                    .filter_map(|expr| self.create_element_child(expr)),
+            );
+        }
+
+        let location = self.location(call.span);
+        let raw_text = normalize_indentation(call.span.source_text(self.source_text));
+
+        Some(
+            JSXElementOccurrence::new(location, tag_name, attributes, raw_text, children)
+                .with_context(self.scope.context()),
+        )
+    }
+
+    /// Extract attributes and children from the props argument of a createElement or jsx call
+    ///
+    /// Properties of an object literal become regular attributes, spread properties and other
+    /// expressions become spread attributes and a `children` property becomes the children.
+    fn extract_call_props(
+        &self,
+        props: &oxc::ast::ast::Expression,
+        attributes: &mut Vec<JSXAttribute>,
+        children: &mut Vec<ChildNode>,
+    ) {
+        use oxc::ast::ast::{ArrayExpressionElement, Expression, ObjectPropertyKind};
+
+        let object = match props.without_parentheses() {
+            Expression::ObjectExpression(object) => object,
+            // createElement(Button, null)
+            Expression::NullLiteral(_) => return,
+            Expression::Identifier(id) if id.name == "undefined" => return,
+            other => {
+                attributes.push(JSXAttribute::Spread(analyze_spread_attribute(
+                    other,
+                    &self.variables,
+                    self.source_text,
+                )));
+                return;
+            }
+        };
+
+        for property in &object.properties {
+            match property {
+                ObjectPropertyKind::ObjectProperty(property) => {
+                    let Some(key) = property.key.static_name() else {
+                        continue;
+                    };
+                    if key == "children" {
+                        match property.value.without_parentheses() {
+                            Expression::ArrayExpression(array) => {
+                                children.extend(array.elements.iter().filter_map(|element| {
+                                    match element {
+                                        ArrayExpressionElement::SpreadElement(_) => None,
+                                        element => {
+                                            self.create_element_child(element.as_expression()?)
+                                        }
+                                    }
+                                }))
+                            }
+                            value => children.extend(self.create_element_child(value)),
+                        }
+                        continue;
+                    }
+
+                    let value = match property.value.without_parentheses() {
+                        Expression::StringLiteral(s) => {
+                            PropValue::StringLiteral(s.value.to_string())
+                        }
+                        value => analyze_expression_value(value, self.source_text),
+                    };
+                    attributes.push(JSXAttribute::Regular(PropAssignment::new(
+                        PropName::Simple(key.to_string()),
+                        value,
+                    )));
+                }
+                ObjectPropertyKind::SpreadProperty(spread) => {
+                    attributes.push(JSXAttribute::Spread(analyze_spread_attribute(
+                        &spread.argument,
+                        &self.variables,
+                        self.source_text,
+                    )));
+                }
+            }
+        }
+    }
+
+    /// Convert a child argument of a createElement or jsx call to a ChildNode
+    ///
+    /// Strings are the compiled form of JSX text and nested calls of JSX elements.
+    fn create_element_child(&self, expr: &oxc::ast::ast::Expression) -> Option<ChildNode> {
+        use oxc::ast::ast::Expression;
+
+        match expr.without_parentheses() {
+            Expression::StringLiteral(s) => {
+                let text = normalize_text(&s.value);
+                (!text.is_empty()).then_some(ChildNode::Text(text))
+            }
+            Expression::NullLiteral(_) => None,
+            Expression::CallExpression(call) => {
+                let tag_name = self
+                    .create_element_call_kind(&call.callee)
+                    .and_then(|_| create_element_type(call.arguments.first()?.as_expression()?));
+                match tag_name {
+                    Some(tag_name) => Some(ChildNode::JSXElement {
+                        tag: tag_name.display_name(),
+                        raw: normalize_indentation(call.span.source_text(self.source_text)),
+                    }),
+                    None => Some(expression_to_child_node(expr)),
+                }
+            }
+            expr => Some(expression_to_child_node(expr)),
+        }
+    }
+
+    /// Recognize `createElement` and jsx runtime calls
+    ///
+    /// The callee must come from React: `React.createElement`, `createElement`, `_jsx`,
+    /// `_jsxRuntime.jsx` and the compiled `(0, _jsxRuntime.jsx)` form are accepted when they
+    /// are bound to `react`, `react/jsx-runtime` or `react/jsx-dev-runtime`.
+    fn create_element_call_kind(
+        &self,
+        callee: &oxc::ast::ast::Expression,
+    ) -> Option<CreateElementCall> {
+        match self.callee_export(callee)? {
+            ("react", "createElement") => Some(CreateElementCall::CreateElement),
+            ("react/jsx-runtime" | "react/jsx-dev-runtime", "jsx" | "jsxs" | "jsxDEV") => {
+                Some(CreateElementCall::JsxRuntime)
+            }
+            _ => None,
+        }
+    }
+
+    /// `lazy` or `React.lazy` from `react`, and `dynamic` from `next/dynamic`
+    fn is_lazy_loader(&self, callee: &oxc::ast::ast::Expression) -> bool {
+        matches!(
+            self.callee_export(callee),
+            Some(("react", "lazy") | ("next/dynamic", "default"))
+        )
+    }
+
+    /// Module and export name a callee refers to
+    ///
+    /// Follows named imports (`createElement`), members of default and namespace imports
+    /// (`React.createElement`), destructured aliases (`const { lazy } = React`) and the
+    /// `(0, fn)` form emitted by compilers. Only bindings seen so far are known.
+    fn callee_export<'s>(
+        &'s self,
+        callee: &'s oxc::ast::ast::Expression,
+    ) -> Option<(&'s str, &'s str)> {
+        use oxc::ast::ast::Expression;
+
+        let whole_module = |binding: &ImportBinding| {
+            binding.member_path().is_empty()
+                && matches!(
+                    binding.imported_name(),
+                    ImportedName::Default | ImportedName::Namespace
+                )
+        };
+
+        match callee.without_parentheses() {
+            Expression::Identifier(id) => {
+                if let Some(binding) = self.binding(&id.name) {
+                    let export = match binding.imported_name() {
+                        ImportedName::Named(name) if binding.member_path().is_empty() => {
+                            name.as_str()
+                        }
+                        ImportedName::Default if binding.member_path().is_empty() => "default",
+                        _ => return None,
+                    };
+                    return Some((binding.source().as_str(), export));
+                }
+                let alias = self
+                    .aliases
+                    .iter()
+                    .rev()
+                    .find(|alias| alias.local_name == id.name.as_str())?;
+                let [export] = alias.members.as_slice() else {
+                    return None;
+                };
+                let binding = self.binding(&alias.target).filter(|b| whole_module(b))?;
+                Some((binding.source().as_str(), export.as_str()))
+            }
+            Expression::StaticMemberExpression(member) => {
+                let Expression::Identifier(object) = member.object.without_parentheses() else {
+                    return None;
+                };
+                let binding = self.binding(&object.name).filter(|b| whole_module(b))?;
+                Some((binding.source().as_str(), member.property.name.as_str()))
+            }
+            Expression::SequenceExpression(sequence) => {
+                self.callee_export(sequence.expressions.last()?)
+            }
+            _ => None,
+        }
+    }
+
+    /// Binding of a local name from an import or a `require()` seen so far
+    fn binding(&self, local_name: &str) -> Option<&ImportBinding> {
+        self.imports
+            .iter()
+            .chain(self.dynamic_imports.iter())
+            .find(|binding| binding.local_name() == local_name)
+    }
+
+    /// Location in the document of a span of the parsed code
+    /// Byte range of the span in the document
+    fn range(&self, span: oxc::span::Span) -> std::ops::Range<u32> {
+        self.offset + span.start..self.offset + span.end
+    }
+
+    fn location(&self, span: oxc::span::Span) -> SourceLocation {
+        let span = self
+            .lines
+            .span(self.offset + span.start, self.offset + span.end);
+        SourceLocation::new(self.source_file.clone(), span)
+    }
+
+    /// Visit the arguments of a createElement call like the props and children of an element
+    fn visit_create_element_arguments(&mut self, call: &oxc::ast::ast::CallExpression) {
+        use oxc::ast::ast::{Expression, ObjectPropertyKind, PropertyKey};
+
+        self.visit_expression(&call.callee);
+        for (index, argument) in call.arguments.iter().enumerate() {
+            let Some(expr) = argument.as_expression() else {
+                self.visit_argument(argument);
+                continue;
+            };
+            match (index, expr.without_parentheses()) {
+                (1, Expression::ObjectExpression(object)) => {
+                    for property in &object.properties {
+                        let ObjectPropertyKind::ObjectProperty(property) = property else {
+                            self.visit_object_property_kind(property);
+                            continue;
+                        };
+                        let is_children = matches!(
+                            &property.key,
+                            PropertyKey::StaticIdentifier(key) if key.name == "children"
+                        );
+                        if is_children {
+                            self.visit_children_argument(&property.value);
+                        } else {
+                            let counter = if is_function(&property.value) {
+                                render_props_of
+                            } else {
+                                props_of
+                            };
+                            self.nested(counter, |v| v.visit_expression(&property.value));
+                        }
+                    }
+                }
+                (0 | 1, _) => self.visit_expression(expr),
+                _ => self.visit_children_argument(expr),
+            }
+        }
+    }
+
+    fn visit_children_argument(&mut self, expr: &oxc::ast::ast::Expression) {
+        if is_function(expr) {
+            self.nested(render_props_of, |v| v.visit_expression(expr));
+        } else {
+            self.visit_expression(expr);
+        }
+    }
+
+    /// Visit with one of the scope counters raised
+    fn nested(
+        &mut self,
+        counter: fn(&mut RenderScope) -> &mut usize,
+        visit: impl FnOnce(&mut Self),
+    ) {
+        *counter(&mut self.scope) += 1;
+        visit(self);
+        *counter(&mut self.scope) -= 1;
+    }
+}
+
+fn loops_of(scope: &mut RenderScope) -> &mut usize {
+    &mut scope.loops
+}
+
+fn conditionals_of(scope: &mut RenderScope) -> &mut usize {
+    &mut scope.conditionals
+}
+
+fn props_of(scope: &mut RenderScope) -> &mut usize {
+    &mut scope.props
+}
+
+fn render_props_of(scope: &mut RenderScope) -> &mut usize {
+    &mut scope.render_props
+}
+
+fn is_function(expr: &oxc::ast::ast::Expression) -> bool {
+    expr.without_parentheses().is_function()
+}
+
+/// `items.map(...)` and `items.flatMap(...)`
+fn is_map_call(callee: &oxc::ast::ast::Expression) -> bool {
+    matches!(
+        callee.without_parentheses(),
+        oxc::ast::ast::Expression::StaticMemberExpression(member)
+            if matches!(member.property.name.as_str(), "map" | "flatMap")
+    )
+}
+
+/// Converts the elements of an Astro or Vue template into element occurrences
+struct TemplateConverter<'b> {
+    source_text: &'b str,
+    source_file: &'b SourceFile,
+    lines: &'b LineIndex<'b>,
+    template: &'b Template,
+    syntax: TemplateSyntax,
+    expression_type: SourceType,
+    /// Local names of the imports, used to map `<my-button>` to `MyButton`
+    bindings: HashSet<&'b str>,
+}
+
+impl TemplateConverter<'_> {
+    fn occurrence(&self, element: &TemplateElement) -> JSXElementOccurrence {
+        let span = self
+            .lines
+            .span(element.span.start as u32, element.span.end as u32);
+        let location = SourceLocation::new(self.source_file.clone(), span);
+        let raw_text = normalize_indentation(&self.source_text[element.span.clone()]);
+        let attributes = element
+            .attributes
+            .iter()
+            .filter_map(|attribute| self.attribute(attribute))
+            .collect();
+        let children = element
+            .children
+            .iter()
+            .filter_map(|child| self.child(child))
+            .collect();
+
+        JSXElementOccurrence::new(
+            location,
+            self.tag_name(&element.name),
+            attributes,
+            raw_text,
+            children,
+        )
+    }
+
+    /// Rendering context of each element, inherited from its parents
+    ///
+    /// In Vue, `v-for` renders an element in a loop, `v-if` and its siblings render it
+    /// conditionally, and a scoped slot (`#item="{ row }"`) is a render function for the
+    /// elements in it.
+    fn contexts(&self) -> Vec<RenderContext> {
+        let mut contexts = vec![RenderContext::default(); self.template.elements.len()];
+        // Parents come before their children
+        for (index, element) in self.template.elements.iter().enumerate() {
+            let mut context = contexts[index].clone();
+            let mut scoped_slot = false;
+            if self.syntax == TemplateSyntax::Vue {
+                for attribute in &element.attributes {
+                    let TemplateAttribute::Named { name, value } = attribute else {
+                        continue;
+                    };
+                    match name.as_str() {
+                        "v-for" => context.in_loop = true,
+                        "v-if" | "v-else-if" | "v-else" => context.conditional = true,
+                        _ if name.starts_with('#') || name.starts_with("v-slot") => {
+                            scoped_slot = value.is_some();
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            contexts[index] = context.clone();
+
+            context.in_render_prop |= scoped_slot;
+            for child in &element.children {
+                if let TemplateChild::Element(child) = child {
+                    contexts[*child] = context.clone();
+                }
+            }
+        }
+        contexts
+    }
+
+    fn tag_name(&self, name: &str) -> JSXElementReference {
+        if let Some((object, members)) = name.split_once('.') {
+            return JSXElementReference::member(
+                object,
+                members.split('.').map(str::to_string).collect(),
+            );
+        }
+        if name.contains('-') {
+            let pascal_case: String = name
+                .split('-')
+                .map(|part| {
+                    let mut chars = part.chars();
+                    chars
+                        .next()
+                        .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                        .unwrap_or_default()
+                })
+                .collect();
+            if self.bindings.contains(pascal_case.as_str()) {
+                return JSXElementReference::direct(pascal_case);
+            }
+        }
+        JSXElementReference::direct(name)
+    }
+
+    fn attribute(&self, attribute: &TemplateAttribute) -> Option<JSXAttribute> {
+        let (name, value) = match attribute {
+            TemplateAttribute::Spread(range) => {
+                return Some(JSXAttribute::Spread(self.spread(range)));
+            }
+            TemplateAttribute::Shorthand(range) => {
+                let value = self.expression_value(range);
+                return Some(JSXAttribute::Regular(PropAssignment::new(
+                    template_prop_name(&self.text(range)),
+                    value,
+                )));
+            }
+            TemplateAttribute::Named { name, value } => (name, value),
+        };
+
+        let binding = match self.syntax {
+            TemplateSyntax::Astro => AttributeBinding::Static(name.clone()),
+            TemplateSyntax::Vue => vue_binding(name),
+        };
+        let (name, value) = match (binding, value) {
+            (AttributeBinding::Skip, _) => return None,
+            (AttributeBinding::Spread, Some(AttributeValue::Text(range))) => {
+                return Some(JSXAttribute::Spread(self.spread(range)));
+            }
+            (AttributeBinding::Spread, _) => return None,
+            (AttributeBinding::Static(name), None) => (name, PropValue::BooleanImplicit),
+            (AttributeBinding::Static(name), Some(AttributeValue::Text(range))) => (
+                name,
+                PropValue::StringLiteral(self.source_text[range.clone()].to_string()),
+            ),
+            (AttributeBinding::Bound(name), Some(AttributeValue::Text(range)))
+            | (
+                AttributeBinding::Bound(name) | AttributeBinding::Static(name),
+                Some(AttributeValue::Expression(range)),
+            ) => (name, self.expression_value(range)),
+            // `:id` is short for `:id="id"`
+            (AttributeBinding::Bound(name), None) => {
+                let value = PropValue::Expression {
+                    raw: name.clone(),
+                    kind: ExpressionKind::Identifier,
+                    shape: None,
+                    type_annotation: None,
+                };
+                (name, value)
+            }
+        };
+        Some(JSXAttribute::Regular(PropAssignment::new(
+            template_prop_name(&name),
+            value,
+        )))
+    }
+
+    fn spread(&self, range: &std::ops::Range<usize>) -> SpreadAttribute {
+        self.with_expression(range, |expr, source_text| {
+            analyze_spread_attribute(expr, &std::collections::HashMap::new(), source_text)
+        })
+        .unwrap_or_else(|| SpreadAttribute::new(self.text(range)))
+    }
+
+    fn child(&self, child: &TemplateChild) -> Option<ChildNode> {
+        match child {
+            TemplateChild::Text(range) => Some(ChildNode::Text(self.text(range))),
+            TemplateChild::Element(index) => {
+                let element = &self.template.elements[*index];
+                if self.syntax == TemplateSyntax::Vue && element.name == "template" {
+                    return None;
+                }
+                Some(ChildNode::JSXElement {
+                    tag: self.tag_name(&element.name).display_name(),
+                    raw: normalize_indentation(&self.source_text[element.span.clone()]),
+                })
+            }
+            TemplateChild::Expression(range) => Some(
+                self.with_expression(range, |expr, _| expression_to_child_node(expr))
+                    .unwrap_or_else(|| ChildNode::Expression {
+                        raw: "<expression>".to_string(),
+                        kind: ExpressionKind::Complex,
+                    }),
+            ),
+        }
+    }
+
+    /// Analyze the expression at `range` as a prop value
+    fn expression_value(&self, range: &std::ops::Range<usize>) -> PropValue {
+        self.with_expression(range, analyze_expression_value)
+            .unwrap_or_else(|| PropValue::Expression {
+                raw: self.text(range),
+                kind: ExpressionKind::Complex,
+                shape: None,
+                type_annotation: None,
+            })
+    }
+
+    /// Parse the expression at `range` on its own
+    ///
+    /// Spans of the expression are relative to the range, whose text is passed along with it.
+    fn with_expression<R>(
+        &self,
+        range: &std::ops::Range<usize>,
+        f: impl FnOnce(&oxc::ast::ast::Expression, &str) -> R,
+    ) -> Option<R> {
+        let code = &self.source_text[range.clone()];
+        let allocator = Allocator::default();
+        let expression = Parser::new(&allocator, code, self.expression_type)
+            .parse_expression()
+            .ok()?;
+        Some(f(&expression, code))
+    }
+
+    fn text(&self, range: &std::ops::Range<usize>) -> String {
+        normalize_text(&self.source_text[range.clone()])
+    }
+}
+
+/// Prop set by a template attribute
+enum AttributeBinding {
+    /// `name="text"`
+    Static(String),
+    /// `:name="expression"`, `@event="handler"` or `v-model="value"`
+    Bound(String),
+    /// `v-bind="object"`
+    Spread,
+    /// Other directives such as `v-if` or `#slot`
+    Skip,
+}
+
+/// Map a Vue attribute to the prop it sets
+///
+/// Event listeners become `on` props (`@click` sets `onClick`) and `v-model` sets
+/// `modelValue`, as they do for components. Modifiers such as `.prevent` are dropped.
+fn vue_binding(name: &str) -> AttributeBinding {
+    let without_modifiers = |arg: &str| arg.split('.').next().unwrap_or_default().to_string();
+
+    if let Some(arg) = name
+        .strip_prefix(':')
+        .or_else(|| name.strip_prefix("v-bind:"))
+    {
+        AttributeBinding::Bound(without_modifiers(arg))
+    } else if name == "v-bind" {
+        AttributeBinding::Spread
+    } else if let Some(event) = name
+        .strip_prefix('@')
+        .or_else(|| name.strip_prefix("v-on:"))
+    {
+        let event = without_modifiers(event);
+        let mut chars = event.chars();
+        let capitalized: String = chars
+            .next()
+            .map(|first| first.to_uppercase().chain(chars).collect())
+            .unwrap_or_default();
+        AttributeBinding::Bound(format!("on{capitalized}"))
+    } else if name == "v-model" || name.starts_with("v-model.") {
+        AttributeBinding::Bound("modelValue".to_string())
+    } else if let Some(arg) = name.strip_prefix("v-model:") {
+        AttributeBinding::Bound(without_modifiers(arg))
+    } else if name.starts_with("v-") || name.starts_with('#') {
+        AttributeBinding::Skip
+    } else {
+        AttributeBinding::Static(name.to_string())
+    }
+}
+
+/// `client:load` is namespaced like `xlink:href` in JSX
+fn template_prop_name(name: &str) -> PropName {
+    match name.split_once(':') {
+        Some((namespace, name)) => PropName::Namespaced {
+            namespace: namespace.to_string(),
+            name: name.to_string(),
+        },
+        None => PropName::Simple(name.to_string()),
+    }
 }
 
 /// Extract children of a JSX element
@@ -224,9 +1271,23 @@ fn extract_jsx_text(text: &JSXText) -> String {
 
 /// Convert JSXExpression to ChildNode
 fn analyze_jsx_expression_to_child_node(expression: &JSXExpression) -> ChildNode {
+    match expression.as_expression() {
+        Some(expression) => expression_to_child_node(expression),
+        // Empty expression
+        None => ChildNode::Expression {
+            raw: "<expression>".to_string(),
+            kind: ExpressionKind::Complex,
+        },
+    }
+}
+
+/// Convert an expression child to ChildNode
+fn expression_to_child_node(expression: &oxc::ast::ast::Expression) -> ChildNode {
+    use oxc::ast::ast::Expression;
+
     match expression {
         // Literal values
-        JSXExpression::NumericLiteral(num) => ChildNode::Expression {
+        Expression::NumericLiteral(num) => ChildNode::Expression {
             raw: num
                 .raw
                 .as_ref()
@@ -234,27 +1295,27 @@ fn analyze_jsx_expression_to_child_node(expression: &JSXExpression) -> ChildNode
                 .unwrap_or_else(|| num.value.to_string()),
             kind: ExpressionKind::Literal,
         },
-        JSXExpression::BooleanLiteral(bool_lit) => ChildNode::Expression {
+        Expression::BooleanLiteral(bool_lit) => ChildNode::Expression {
             raw: bool_lit.value.to_string(),
             kind: ExpressionKind::Literal,
         },
-        JSXExpression::StringLiteral(str_lit) => ChildNode::Expression {
+        Expression::StringLiteral(str_lit) => ChildNode::Expression {
             raw: str_lit.value.to_string(),
             kind: ExpressionKind::Literal,
         },
-        JSXExpression::NullLiteral(_) => ChildNode::Expression {
+        Expression::NullLiteral(_) => ChildNode::Expression {
             raw: "null".to_string(),
             kind: ExpressionKind::Literal,
         },
 
         // Identifier
-        JSXExpression::Identifier(id) => ChildNode::Expression {
+        Expression::Identifier(id) => ChildNode::Expression {
             raw: id.name.to_string(),
             kind: ExpressionKind::Identifier,
         },
 
         // JSX elements/fragments
-        JSXExpression::JSXElement(element) => {
+        Expression::JSXElement(element) => {
             let tag_name = format_component_name(&element.opening_element.name);
             // Simplified representation for JSX elements within expressions
             ChildNode::JSXElement {
@@ -262,47 +1323,397 @@ fn analyze_jsx_expression_to_child_node(expression: &JSXExpression) -> ChildNode
                 raw: format!("<{} />", tag_name.display_name()),
             }
         }
-        JSXExpression::JSXFragment(_) => ChildNode::JSXFragment,
+        Expression::JSXFragment(_) => ChildNode::JSXFragment,
 
         // Conditional expression
-        JSXExpression::ConditionalExpression(_) => ChildNode::Expression {
-            raw: "<conditional>".to_string(),
-            kind: ExpressionKind::ConditionalExpression,
-        },
+        Expression::ConditionalExpression(cond) => {
+            let mut values = Vec::new();
+            collect_branch_values(&cond.consequent, &mut values);
+            collect_branch_values(&cond.alternate, &mut values);
+            ChildNode::Expression {
+                raw: "<conditional>".to_string(),
+                kind: ExpressionKind::ConditionalExpression { values },
+            }
+        }
 
         // Arrow function
-        JSXExpression::ArrowFunctionExpression(_) => ChildNode::Expression {
+        Expression::ArrowFunctionExpression(_) => ChildNode::Expression {
             raw: "() => {}".to_string(),
             kind: ExpressionKind::ArrowFunction,
         },
 
-        // Other complex expressions
-        _ => ChildNode::Expression {
-            raw: "<expression>".to_string(),
-            kind: ExpressionKind::Complex,
+        // Template literals, logical, unary and binary expressions
+        _ => {
+            let (kind, raw) = analyze_operator_expression(expression)
+                .unwrap_or_else(|| (ExpressionKind::Complex, "<expression>".to_string()));
+            ChildNode::Expression { raw, kind }
+        }
+    }
+}
+
+/// Relative specifiers a module depends on through imports, re-exports, `require()` and
+/// `import()`
+pub(crate) fn relative_imports(source_text: &str, path: &Path) -> Vec<String> {
+    let source_type = SourceType::from_path(path)
+        .unwrap_or_default()
+        .with_jsx(true);
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, source_text, source_type).parse();
+
+    let mut collector = RequireCollector::default();
+    collector.visit_program(&ret.program);
+
+    ret.module_record
+        .requested_modules
+        .keys()
+        .map(|specifier| specifier.to_string())
+        .chain(collector.specifiers)
+        .filter(|specifier| specifier.starts_with("./") || specifier.starts_with("../"))
+        .collect()
+}
+
+/// Collects the string specifiers of `require()` calls and `import()` expressions
+#[derive(Default)]
+struct RequireCollector {
+    specifiers: Vec<String>,
+}
+
+impl<'a> Visit<'a> for RequireCollector {
+    fn visit_call_expression(&mut self, it: &oxc::ast::ast::CallExpression<'a>) {
+        if it.is_require_call() {
+            if let Some(oxc::ast::ast::Argument::StringLiteral(s)) = it.arguments.first() {
+                self.specifiers.push(s.value.to_string());
+            }
+        }
+        oxc_ast_visit::walk::walk_call_expression(self, it);
+    }
+
+    fn visit_import_expression(&mut self, it: &oxc::ast::ast::ImportExpression<'a>) {
+        if let oxc::ast::ast::Expression::StringLiteral(s) = &it.source {
+            self.specifiers.push(s.value.to_string());
+        }
+        oxc_ast_visit::walk::walk_import_expression(self, it);
+    }
+}
+
+pub(crate) fn extract_imports(module_record: &ModuleRecord) -> Vec<ImportBinding> {
+    module_record
+        .import_entries
+        .iter()
+        .map(|entry| {
+            let source = ModuleSpecifier::new(entry.module_request.name.to_string());
+            let local_name = entry.local_name.name.to_string();
+
+            let imported_name = match &entry.import_name {
+                ImportImportName::Name(name_span) => {
+                    ImportedName::Named(name_span.name.to_string())
+                }
+                ImportImportName::Default(_) => ImportedName::Default,
+                ImportImportName::NamespaceObject => ImportedName::Namespace,
+            };
+
+            ImportBinding::new(source, imported_name, local_name)
+        })
+        .collect()
+}
+
+/// Extract bindings from `require()` calls and `lazy`/`dynamic` loaders in a variable declarator
+fn extract_dynamic_bindings(
+    id: &oxc::ast::ast::BindingPatternKind,
+    init: &oxc::ast::ast::Expression,
+    is_lazy_loader: impl Fn(&oxc::ast::ast::Expression) -> bool,
+) -> Vec<ImportBinding> {
+    use oxc::ast::ast::{BindingPatternKind, Expression};
+
+    let init = init.without_parentheses();
+
+    // const Modal = lazy(() => import("./Modal"))
+    if let (BindingPatternKind::BindingIdentifier(local), Expression::CallExpression(call)) =
+        (id, init)
+    {
+        if is_lazy_loader(&call.callee) {
+            return call
+                .arguments
+                .first()
+                .and_then(|arg| arg.as_expression())
+                .and_then(extract_lazy_import)
+                .map(|(source, export)| {
+                    ImportBinding::new(
+                        ModuleSpecifier::new(source),
+                        ImportedName::Lazy(export),
+                        local.name.to_string(),
+                    )
+                })
+                .into_iter()
+                .collect();
+        }
+    }
+
+    // const UI = require("ui") / const Button = require("ui").Button
+    if let BindingPatternKind::BindingIdentifier(local) = id {
+        if let Some(source) = require_source(init) {
+            return vec![ImportBinding::new(
+                ModuleSpecifier::new(source),
+                ImportedName::Namespace,
+                local.name.to_string(),
+            )];
+        }
+        if let Expression::StaticMemberExpression(member) = init {
+            if let Some(source) = require_source(&member.object) {
+                return vec![ImportBinding::new(
+                    ModuleSpecifier::new(source),
+                    imported_name_for_export(member.property.name.as_str()),
+                    local.name.to_string(),
+                )];
+            }
+        }
+        return Vec::new();
+    }
+
+    // const { Button, Dialog: Modal } = require("ui")
+    if let BindingPatternKind::ObjectPattern(pattern) = id {
+        if let Some(source) = require_source(init) {
+            return pattern
+                .properties
+                .iter()
+                .filter(|property| !property.computed)
+                .filter_map(|property| {
+                    let export = property.key.static_name()?;
+                    let BindingPatternKind::BindingIdentifier(local) = &property.value.kind else {
+                        return None;
+                    };
+                    Some(ImportBinding::new(
+                        ModuleSpecifier::new(source.clone()),
+                        imported_name_for_export(&export),
+                        local.name.to_string(),
+                    ))
+                })
+                .collect();
+        }
+    }
+
+    Vec::new()
+}
+
+/// Extract aliases from `const Btn = UI.Button`, `const { Button } = UI` and `const B = Button`
+fn extract_aliases(
+    id: &oxc::ast::ast::BindingPatternKind,
+    init: &oxc::ast::ast::Expression,
+    scope: Option<std::ops::Range<u32>>,
+) -> Vec<LocalAlias> {
+    use oxc::ast::ast::BindingPatternKind;
+
+    let Some((target, members)) = identifier_member_path(init) else {
+        return Vec::new();
+    };
+
+    match id {
+        BindingPatternKind::BindingIdentifier(local) => vec![LocalAlias {
+            local_name: local.name.to_string(),
+            target,
+            members,
+            scope,
+        }],
+        BindingPatternKind::ObjectPattern(pattern) => pattern
+            .properties
+            .iter()
+            .filter(|property| !property.computed)
+            .filter_map(|property| {
+                let key = property.key.static_name()?;
+                let BindingPatternKind::BindingIdentifier(local) = &property.value.kind else {
+                    return None;
+                };
+                let mut members = members.clone();
+                members.push(key.to_string());
+                Some(LocalAlias {
+                    local_name: local.name.to_string(),
+                    target: target.clone(),
+                    members,
+                    scope: scope.clone(),
+                })
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Split `UI.Forms.Button` into `("UI", ["Forms", "Button"])`
+fn identifier_member_path(expr: &oxc::ast::ast::Expression) -> Option<(String, Vec<String>)> {
+    use oxc::ast::ast::Expression;
+
+    match expr.without_parentheses() {
+        Expression::Identifier(id) => Some((id.name.to_string(), Vec::new())),
+        Expression::StaticMemberExpression(member) => {
+            let (root, mut members) = identifier_member_path(&member.object)?;
+            members.push(member.property.name.to_string());
+            Some((root, members))
+        }
+        _ => None,
+    }
+}
+
+/// Resolve aliases back to the import they were derived from
+///
+/// Aliases may chain (`const A = UI; const B = A.Button`), so resolution repeats until
+/// no more aliases can be attached to a known binding. An alias only chains onto aliases
+/// visible in the function declaring it.
+fn resolve_aliases(imports: &[ImportBinding], aliases: &[LocalAlias]) -> Vec<AliasBinding> {
+    let mut resolved: Vec<AliasBinding> = Vec::new();
+    let mut pending: Vec<&LocalAlias> = aliases
+        .iter()
+        .filter(|alias| !imports.iter().any(|i| i.local_name() == alias.local_name))
+        .collect();
+
+    loop {
+        let before = pending.len();
+        pending.retain(|alias| {
+            let visible = |scope: &Option<std::ops::Range<u32>>| match (scope, &alias.scope) {
+                (None, _) => true,
+                (Some(outer), Some(inner)) => outer.start <= inner.start && inner.end <= outer.end,
+                (Some(_), None) => false,
+            };
+            let base = imports
+                .iter()
+                .find(|binding| binding.local_name() == alias.target)
+                .or_else(|| {
+                    resolved
+                        .iter()
+                        .filter(|base| visible(&base.scope))
+                        .map(|base| &base.binding)
+                        .find(|binding| binding.local_name() == alias.target)
+                })
+                .cloned();
+            match base {
+                Some(base) => {
+                    resolved.push(AliasBinding {
+                        binding: base.derive(alias.local_name.clone(), &alias.members),
+                        scope: alias.scope.clone(),
+                    });
+                    false
+                }
+                None => true,
+            }
+        });
+        if pending.is_empty() || pending.len() == before {
+            break;
+        }
+    }
+
+    resolved
+}
+
+/// Return the module specifier if the expression is `require("...")`
+fn require_source(expr: &oxc::ast::ast::Expression) -> Option<String> {
+    use oxc::ast::ast::{Argument, Expression};
+
+    let Expression::CallExpression(call) = expr.without_parentheses() else {
+        return None;
+    };
+    if !call.is_require_call() {
+        return None;
+    }
+    match call.arguments.first()? {
+        Argument::StringLiteral(s) => Some(s.value.to_string()),
+        _ => None,
+    }
+}
+
+fn imported_name_for_export(export: &str) -> ImportedName {
+    if export == "default" {
+        ImportedName::Default
+    } else {
+        ImportedName::Named(export.to_string())
+    }
+}
+
+/// Extract the module specifier and export name loaded by a loader function
+///
+/// Supported forms:
+/// - `() => import("./Modal")` (default export)
+/// - `() => import("./Modal").then((m) => m.Modal)`
+/// - `() => import("./Modal").then((m) => ({ default: m.Modal }))`
+fn extract_lazy_import(loader: &oxc::ast::ast::Expression) -> Option<(String, String)> {
+    use oxc::ast::ast::{Expression, Statement};
+
+    let body = match loader.without_parentheses() {
+        Expression::ArrowFunctionExpression(arrow) => match arrow.get_expression() {
+            Some(expr) => expr,
+            None => arrow.body.statements.iter().find_map(|stmt| match stmt {
+                Statement::ReturnStatement(ret) => ret.argument.as_ref(),
+                _ => None,
+            })?,
         },
+        Expression::FunctionExpression(func) => {
+            func.body
+                .as_ref()?
+                .statements
+                .iter()
+                .find_map(|stmt| match stmt {
+                    Statement::ReturnStatement(ret) => ret.argument.as_ref(),
+                    _ => None,
+                })?
+        }
+        _ => return None,
+    };
+
+    match body.without_parentheses() {
+        Expression::CallExpression(call) => {
+            let Expression::StaticMemberExpression(then) = &call.callee else {
+                return None;
+            };
+            if then.property.name != "then" {
+                return None;
+            }
+            let source = dynamic_import_source(&then.object)?;
+            let export = call
+                .arguments
+                .first()
+                .and_then(|arg| arg.as_expression())
+                .and_then(extract_then_export)?;
+            Some((source, export))
+        }
+        expr => dynamic_import_source(expr).map(|source| (source, "default".to_string())),
     }
 }
 
-fn extract_imports(module_record: &ModuleRecord) -> Vec<ImportBinding> {
-    module_record
-        .import_entries
-        .iter()
-        .map(|entry| {
-            let source = ModuleSpecifier::new(entry.module_request.name.to_string());
-            let local_name = entry.local_name.name.to_string();
+/// Return the module specifier if the expression is `import("...")`
+fn dynamic_import_source(expr: &oxc::ast::ast::Expression) -> Option<String> {
+    use oxc::ast::ast::Expression;
 
-            let imported_name = match &entry.import_name {
-                ImportImportName::Name(name_span) => {
-                    ImportedName::Named(name_span.name.to_string())
-                }
-                ImportImportName::Default(_) => ImportedName::Default,
-                ImportImportName::NamespaceObject => ImportedName::Namespace,
-            };
+    let Expression::ImportExpression(import) = expr.without_parentheses() else {
+        return None;
+    };
+    match &import.source {
+        Expression::StringLiteral(source) => Some(source.value.to_string()),
+        _ => None,
+    }
+}
 
-            ImportBinding::new(source, imported_name, local_name)
-        })
-        .collect()
+/// `(m) => m.Modal` or `(m) => ({ default: m.Modal })`
+fn extract_then_export(callback: &oxc::ast::ast::Expression) -> Option<String> {
+    use oxc::ast::ast::{Expression, ObjectPropertyKind};
+
+    let Expression::ArrowFunctionExpression(arrow) = callback.without_parentheses() else {
+        return None;
+    };
+
+    match arrow.get_expression()?.without_parentheses() {
+        Expression::StaticMemberExpression(member) => Some(member.property.name.to_string()),
+        Expression::ObjectExpression(obj) => obj.properties.iter().find_map(|prop| match prop {
+            ObjectPropertyKind::ObjectProperty(prop)
+                if prop.key.static_name().as_deref() == Some("default") =>
+            {
+                match prop.value.without_parentheses() {
+                    Expression::StaticMemberExpression(member) => {
+                        Some(member.property.name.to_string())
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }),
+        _ => None,
+    }
 }
 
 fn format_component_name(name: &JSXElementName) -> JSXElementReference {
@@ -315,62 +1726,186 @@ fn format_component_name(name: &JSXElementName) -> JSXElementReference {
             JSXElementReference::direct(format!("{}:{}", it.namespace.name, it.name))
         }
         JSXElementName::MemberExpression(it) => {
-            let full_name = format!("{}.{}", it.object, it.property);
-            let parts: Vec<String> = full_name.split('.').map(|s| s.to_string()).collect();
-            if parts.len() >= 2 {
-                let object = parts[0].clone();
-                let members = parts[1..].to_vec();
-                JSXElementReference::member(object, members)
-            } else {
-                JSXElementReference::direct(full_name)
-            }
+            let (object, members) = jsx_member_path(it);
+            JSXElementReference::member(object, members)
         }
         JSXElementName::ThisExpression(_) => JSXElementReference::direct("this"),
     }
 }
 
+/// Split `<Menu.Item.Icon>` into `("Menu", ["Item", "Icon"])`
+fn jsx_member_path(member: &JSXMemberExpression) -> (String, Vec<String>) {
+    let (object, mut members) = match &member.object {
+        JSXMemberExpressionObject::IdentifierReference(id) => (id.name.to_string(), Vec::new()),
+        JSXMemberExpressionObject::MemberExpression(inner) => jsx_member_path(inner),
+        JSXMemberExpressionObject::ThisExpression(_) => ("this".to_string(), Vec::new()),
+    };
+    members.push(member.property.name.to_string());
+    (object, members)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CreateElementCall {
+    /// `createElement(type, props, ...children)`
+    CreateElement,
+    /// `jsx(type, props, key)` from the automatic runtime, also `jsxs` and `jsxDEV`
+    JsxRuntime,
+}
+
+/// Element type passed as the first argument of a createElement or jsx call
+///
+/// Lowercase identifiers hold a type chosen at runtime and are skipped, since they would
+/// otherwise be taken for native elements.
+fn create_element_type(expr: &oxc::ast::ast::Expression) -> Option<JSXElementReference> {
+    use oxc::ast::ast::Expression;
+
+    if let Expression::StringLiteral(s) = expr.without_parentheses() {
+        return Some(JSXElementReference::direct(s.value.to_string()));
+    }
+    let (root, members) = identifier_member_path(expr)?;
+    if members.is_empty() {
+        let is_component = root
+            .trim_start_matches('_')
+            .starts_with(|c: char| c.is_uppercase());
+        is_component.then(|| JSXElementReference::direct(root))
+    } else {
+        Some(JSXElementReference::member(root, members))
+    }
+}
+
 /// Analyze JSXExpression in detail and return PropValue
-fn analyze_jsx_expression(expression: &JSXExpression) -> PropValue {
+///
+/// `raw` is the normalized source text of the expression; literals and identifiers keep their
+/// value. The placeholder used to group similar expressions is kept as `shape`. Parentheses and
+/// TypeScript wrappers (`as`, `satisfies`, `!`, `<T>x`) are looked through, keeping the
+/// outermost type annotation.
+fn analyze_jsx_expression(expression: &JSXExpression, source_text: &str) -> PropValue {
+    // Empty expression
+    let Some(expression) = expression.as_expression() else {
+        return PropValue::Expression {
+            raw: "".to_string(),
+            kind: ExpressionKind::Complex,
+            shape: None,
+            type_annotation: None,
+        };
+    };
+    analyze_expression_value(expression, source_text)
+}
+
+/// Analyze an expression used as a prop value, see [`analyze_jsx_expression`]
+fn analyze_expression_value(
+    expression: &oxc::ast::ast::Expression,
+    source_text: &str,
+) -> PropValue {
+    use oxc::ast::ast::Expression;
+    use oxc::span::GetSpan;
+
+    let (expression, type_annotation) = unwrap_type_expression(expression, source_text);
+
+    let (kind, shape) = match expression {
+        // JSX element
+        Expression::JSXElement(_) => return PropValue::JSXElement("jsx".to_string()),
+        Expression::JSXFragment(_) => return PropValue::JSXFragment,
+        _ => classify_expression(expression, source_text),
+    };
+
+    if matches!(kind, ExpressionKind::Literal | ExpressionKind::Identifier) {
+        return PropValue::Expression {
+            raw: shape,
+            kind,
+            shape: None,
+            type_annotation,
+        };
+    }
+
+    let raw = normalize_text(expression.span().source_text(source_text));
+    let shape = (shape != raw).then_some(shape);
+    PropValue::Expression {
+        raw,
+        kind,
+        shape,
+        type_annotation,
+    }
+}
+
+/// Strip parentheses and TypeScript-only wrappers from an expression
+///
+/// Returns the inner expression and the outermost type annotation as written, e.g. `as const`,
+/// `satisfies Size` or `<Size>`; a lone non-null assertion is recorded as `!`.
+fn unwrap_type_expression<'a, 'b>(
+    mut expr: &'b oxc::ast::ast::Expression<'a>,
+    source_text: &str,
+) -> (&'b oxc::ast::ast::Expression<'a>, Option<String>) {
+    use oxc::ast::ast::Expression;
+    use oxc::span::GetSpan;
+
+    let type_text = |ts_type: &oxc::ast::ast::TSType| ts_type.span().source_text(source_text);
+    let mut annotation: Option<String> = None;
+    let mut non_null = false;
+    loop {
+        expr = match expr {
+            Expression::ParenthesizedExpression(paren) => &paren.expression,
+            Expression::TSAsExpression(ts) => {
+                annotation.get_or_insert_with(|| format!("as {}", type_text(&ts.type_annotation)));
+                &ts.expression
+            }
+            Expression::TSSatisfiesExpression(ts) => {
+                annotation
+                    .get_or_insert_with(|| format!("satisfies {}", type_text(&ts.type_annotation)));
+                &ts.expression
+            }
+            Expression::TSTypeAssertion(ts) => {
+                annotation.get_or_insert_with(|| format!("<{}>", type_text(&ts.type_annotation)));
+                &ts.expression
+            }
+            Expression::TSNonNullExpression(ts) => {
+                non_null = true;
+                &ts.expression
+            }
+            _ => break,
+        };
+    }
+    (
+        expr,
+        annotation.or_else(|| non_null.then(|| "!".to_string())),
+    )
+}
+
+/// Kind and placeholder of a non-JSX expression
+fn classify_expression(
+    expression: &oxc::ast::ast::Expression,
+    source_text: &str,
+) -> (ExpressionKind, String) {
+    use oxc::ast::ast::Expression;
+
     match expression {
         // Literal values
-        JSXExpression::NumericLiteral(num) => PropValue::Expression {
-            raw: num
-                .raw
+        Expression::NumericLiteral(num) => (
+            ExpressionKind::Literal,
+            num.raw
                 .as_ref()
                 .map(|s| s.to_string())
                 .unwrap_or_else(|| num.value.to_string()),
-            kind: ExpressionKind::Literal,
-        },
-        JSXExpression::BooleanLiteral(bool_lit) => PropValue::Expression {
-            raw: bool_lit.value.to_string(),
-            kind: ExpressionKind::Literal,
-        },
-        JSXExpression::NullLiteral(_) => PropValue::Expression {
-            raw: "null".to_string(),
-            kind: ExpressionKind::Literal,
-        },
-        JSXExpression::StringLiteral(str_lit) => PropValue::Expression {
-            raw: str_lit.value.to_string(),
-            kind: ExpressionKind::Literal,
-        },
-        JSXExpression::BigIntLiteral(bigint) => PropValue::Expression {
-            raw: bigint
+        ),
+        Expression::BooleanLiteral(bool_lit) => {
+            (ExpressionKind::Literal, bool_lit.value.to_string())
+        }
+        Expression::NullLiteral(_) => (ExpressionKind::Literal, "null".to_string()),
+        Expression::StringLiteral(str_lit) => (ExpressionKind::Literal, str_lit.value.to_string()),
+        Expression::BigIntLiteral(bigint) => (
+            ExpressionKind::Literal,
+            bigint
                 .raw
                 .as_ref()
                 .map(|s| s.to_string())
                 .unwrap_or_else(|| "<bigint>".to_string()),
-            kind: ExpressionKind::Literal,
-        },
+        ),
 
         // Identifier (variable reference)
-        JSXExpression::Identifier(id) => PropValue::Expression {
-            raw: id.name.to_string(),
-            kind: ExpressionKind::Identifier,
-        },
+        Expression::Identifier(id) => (ExpressionKind::Identifier, id.name.to_string()),
 
         // Member expression
-        JSXExpression::StaticMemberExpression(member) => {
-            use oxc::ast::ast::Expression;
+        Expression::StaticMemberExpression(member) => {
             // Get the name if object is an identifier, otherwise "<expr>"
             let object_str = if let Expression::Identifier(id) = &member.object {
                 id.name.to_string()
@@ -378,83 +1913,224 @@ fn analyze_jsx_expression(expression: &JSXExpression) -> PropValue {
                 "<expr>".to_string()
             };
             let property_str = member.property.name.to_string();
-            PropValue::Expression {
-                raw: format!("{object_str}.{property_str}"),
-                kind: ExpressionKind::MemberExpression,
-            }
+            (
+                ExpressionKind::MemberExpression,
+                format!("{object_str}.{property_str}"),
+            )
         }
-        JSXExpression::ComputedMemberExpression(member) => {
-            use oxc::ast::ast::Expression;
+        Expression::ComputedMemberExpression(member) => {
             let object_str = if let Expression::Identifier(id) = &member.object {
                 id.name.to_string()
             } else {
                 "<expr>".to_string()
             };
-            PropValue::Expression {
-                raw: format!("{object_str}[<computed>]"),
-                kind: ExpressionKind::MemberExpression,
-            }
+            (
+                ExpressionKind::MemberExpression,
+                format!("{object_str}[<computed>]"),
+            )
         }
-        JSXExpression::PrivateFieldExpression(member) => {
-            use oxc::ast::ast::Expression;
+        Expression::PrivateFieldExpression(member) => {
             let object_str = if let Expression::Identifier(id) = &member.object {
                 id.name.to_string()
             } else {
                 "<expr>".to_string()
             };
-            PropValue::Expression {
-                raw: format!("{}.#{}", object_str, member.field.name),
-                kind: ExpressionKind::MemberExpression,
-            }
+            (
+                ExpressionKind::MemberExpression,
+                format!("{}.#{}", object_str, member.field.name),
+            )
         }
 
         // 関数呼び出し
-        JSXExpression::CallExpression(call) => {
-            use oxc::ast::ast::Expression;
+        Expression::CallExpression(call) => {
             let callee_str = if let Expression::Identifier(id) = &call.callee {
                 id.name.to_string()
             } else {
                 "<fn>".to_string()
             };
-            PropValue::Expression {
-                raw: format!("{callee_str}()"),
-                kind: ExpressionKind::CallExpression,
-            }
+            (ExpressionKind::CallExpression, format!("{callee_str}()"))
         }
 
         // アロー関数
-        JSXExpression::ArrowFunctionExpression(_) => PropValue::Expression {
-            raw: "() => {}".to_string(),
-            kind: ExpressionKind::ArrowFunction,
-        },
+        Expression::ArrowFunctionExpression(_) => {
+            (ExpressionKind::ArrowFunction, "() => {}".to_string())
+        }
 
         // Conditional expression (ternary operator)
-        JSXExpression::ConditionalExpression(_) => PropValue::Expression {
-            raw: "<condition> ? <consequent> : <alternate>".to_string(),
-            kind: ExpressionKind::ConditionalExpression,
-        },
+        Expression::ConditionalExpression(cond) => {
+            let mut values = Vec::new();
+            collect_branch_values(&cond.consequent, &mut values);
+            collect_branch_values(&cond.alternate, &mut values);
+            (
+                ExpressionKind::ConditionalExpression { values },
+                "<condition> ? <consequent> : <alternate>".to_string(),
+            )
+        }
 
-        // JSX element
-        JSXExpression::JSXElement(_) => PropValue::JSXElement("jsx".to_string()),
-        JSXExpression::JSXFragment(_) => PropValue::JSXFragment,
+        // Object literal: style={{ margin: 0 }}
+        Expression::ObjectExpression(obj) => {
+            let properties: Vec<ResolvedProp> =
+                extract_object_properties(&obj.properties, source_text)
+                    .into_iter()
+                    .map(ObjectProperty::into_resolved)
+                    .collect();
+            let keys: Vec<&str> = properties.iter().map(|p| p.key()).collect();
+            let shape = if keys.is_empty() {
+                "{}".to_string()
+            } else {
+                format!("{{ {} }}", keys.join(", "))
+            };
+            (ExpressionKind::ObjectLiteral { properties }, shape)
+        }
 
-        // Empty expression
-        JSXExpression::EmptyExpression(_) => PropValue::Expression {
-            raw: "".to_string(),
-            kind: ExpressionKind::Complex,
-        },
+        // Array literal: items={["a", "b"]}
+        Expression::ArrayExpression(array) => {
+            use oxc::ast::ast::ArrayExpressionElement;
+
+            let element_kinds: Vec<String> = array
+                .elements
+                .iter()
+                .map(|element| match element {
+                    ArrayExpressionElement::SpreadElement(_) => "spread".to_string(),
+                    ArrayExpressionElement::Elision(_) => "hole".to_string(),
+                    _ => element
+                        .as_expression()
+                        .map(|expr| analyze_expression_to_pattern_value_raw(expr).0)
+                        .unwrap_or_else(|| "expression".to_string()),
+                })
+                .collect();
+            let shape = format!("[{}]", element_kinds.join(", "));
+            (ExpressionKind::ArrayLiteral { element_kinds }, shape)
+        }
 
-        // Other complex expressions
-        _ => PropValue::Expression {
-            raw: "<expression>".to_string(),
-            kind: ExpressionKind::Complex,
+        // Template literals, logical, unary and binary expressions
+        _ => analyze_operator_expression(expression)
+            .unwrap_or_else(|| (ExpressionKind::Complex, "<expression>".to_string())),
+    }
+}
+
+/// Collect the literal values a conditional branch can take, following nested conditionals
+fn collect_branch_values(expr: &oxc::ast::ast::Expression, values: &mut Vec<String>) {
+    use oxc::ast::ast::Expression;
+
+    let value = match expr.without_parentheses() {
+        Expression::ConditionalExpression(cond) => {
+            collect_branch_values(&cond.consequent, values);
+            collect_branch_values(&cond.alternate, values);
+            return;
+        }
+        Expression::StringLiteral(s) => s.value.to_string(),
+        Expression::NumericLiteral(n) => n
+            .raw
+            .as_ref()
+            .map(|r| r.to_string())
+            .unwrap_or_else(|| n.value.to_string()),
+        Expression::BooleanLiteral(b) => b.value.to_string(),
+        Expression::NullLiteral(_) => "null".to_string(),
+        _ => return,
+    };
+    if !values.contains(&value) {
+        values.push(value);
+    }
+}
+
+/// Classify template literals, logical, unary and binary expressions
+///
+/// Negative numbers (`-1`) are literals. `raw` is a normalized rendering of the expression.
+fn analyze_operator_expression(
+    expr: &oxc::ast::ast::Expression,
+) -> Option<(ExpressionKind, String)> {
+    use oxc::ast::ast::{Expression, UnaryOperator};
+
+    let kind = match expr {
+        Expression::TemplateLiteral(template) => ExpressionKind::TemplateLiteral {
+            quasis: template
+                .quasis
+                .iter()
+                .map(|q| q.value.cooked.as_ref().unwrap_or(&q.value.raw).to_string())
+                .collect(),
         },
+        Expression::UnaryExpression(unary)
+            if matches!(
+                unary.operator,
+                UnaryOperator::UnaryNegation | UnaryOperator::UnaryPlus
+            ) && matches!(unary.argument, Expression::NumericLiteral(_)) =>
+        {
+            ExpressionKind::Literal
+        }
+        Expression::UnaryExpression(_) => ExpressionKind::UnaryExpression,
+        Expression::LogicalExpression(_) => ExpressionKind::LogicalExpression,
+        Expression::BinaryExpression(_) => ExpressionKind::BinaryExpression,
+        _ => return None,
+    };
+    Some((kind, expression_label(expr)))
+}
+
+/// Short rendering of an expression for `raw`, e.g. `` `btn ${size}` `` or `!state.open`
+fn expression_label(expr: &oxc::ast::ast::Expression) -> String {
+    use oxc::ast::ast::Expression;
+
+    match expr {
+        Expression::StringLiteral(s) => format!("\"{}\"", s.value),
+        Expression::NumericLiteral(n) => n
+            .raw
+            .as_ref()
+            .map(|r| r.to_string())
+            .unwrap_or_else(|| n.value.to_string()),
+        Expression::BooleanLiteral(b) => b.value.to_string(),
+        Expression::NullLiteral(_) => "null".to_string(),
+        Expression::Identifier(id) => id.name.to_string(),
+        Expression::ThisExpression(_) => "this".to_string(),
+        Expression::StaticMemberExpression(member) => {
+            format!(
+                "{}.{}",
+                expression_label(&member.object),
+                member.property.name
+            )
+        }
+        Expression::CallExpression(call) => format!("{}()", expression_label(&call.callee)),
+        Expression::ParenthesizedExpression(paren) => {
+            format!("({})", expression_label(&paren.expression))
+        }
+        Expression::TemplateLiteral(template) => {
+            let mut label = String::from("`");
+            for (i, quasi) in template.quasis.iter().enumerate() {
+                label.push_str(&quasi.value.raw);
+                if let Some(expr) = template.expressions.get(i) {
+                    label.push_str(&format!("${{{}}}", expression_label(expr)));
+                }
+            }
+            label.push('`');
+            label
+        }
+        Expression::UnaryExpression(unary) => {
+            let space = if unary.operator.is_keyword() { " " } else { "" };
+            format!(
+                "{}{space}{}",
+                unary.operator.as_str(),
+                expression_label(&unary.argument)
+            )
+        }
+        Expression::LogicalExpression(logical) => format!(
+            "{} {} {}",
+            expression_label(&logical.left),
+            logical.operator.as_str(),
+            expression_label(&logical.right)
+        ),
+        Expression::BinaryExpression(binary) => format!(
+            "{} {} {}",
+            expression_label(&binary.left),
+            binary.operator.as_str(),
+            expression_label(&binary.right)
+        ),
+        _ => "<expr>".to_string(),
     }
 }
 
 fn extract_props(
     jsx_element: &JSXOpeningElement,
     variables: &std::collections::HashMap<String, VariableValue>,
+    source_text: &str,
 ) -> Vec<JSXAttribute> {
     let mut props = Vec::new();
     for attr in &jsx_element.attributes {
@@ -474,7 +2150,7 @@ fn extract_props(
                             PropValue::StringLiteral(string_literal.value.to_string())
                         }
                         JSXAttributeValue::ExpressionContainer(container) => {
-                            analyze_jsx_expression(&container.expression)
+                            analyze_jsx_expression(&container.expression, source_text)
                         }
                         JSXAttributeValue::Element(_) => PropValue::JSXElement("jsx".to_string()),
                         JSXAttributeValue::Fragment(_) => PropValue::JSXFragment,
@@ -486,7 +2162,8 @@ fn extract_props(
                 props.push(JSXAttribute::Regular(PropAssignment::new(key, value)));
             }
             JSXAttributeItem::SpreadAttribute(spread) => {
-                let spread_attr = analyze_spread_attribute(&spread.argument, variables);
+                let spread_attr =
+                    analyze_spread_attribute(&spread.argument, variables, source_text);
                 props.push(JSXAttribute::Spread(spread_attr));
             }
         }
@@ -495,20 +2172,20 @@ fn extract_props(
 }
 
 /// Analyze the value of a variable
-fn analyze_variable_value(expr: &oxc::ast::ast::Expression) -> VariableValue {
+fn analyze_variable_value(expr: &oxc::ast::ast::Expression, source_text: &str) -> VariableValue {
     use oxc::ast::ast::Expression;
 
     match expr {
         // Object literal: { key: value, ... }
         Expression::ObjectExpression(obj) => {
-            let properties = extract_object_properties(&obj.properties);
+            let properties = extract_object_properties(&obj.properties, source_text);
             VariableValue::ObjectLiteral(properties)
         }
         // Conditional expression: condition ? obj1 : obj2
         Expression::ConditionalExpression(cond) => {
             // Analyze and merge both branches
-            let consequent_value = analyze_variable_value(&cond.consequent);
-            let alternate_value = analyze_variable_value(&cond.alternate);
+            let consequent_value = analyze_variable_value(&cond.consequent, source_text);
+            let alternate_value = analyze_variable_value(&cond.alternate, source_text);
             merge_variable_values(consequent_value, alternate_value)
         }
         // Others cannot be analyzed
@@ -517,8 +2194,11 @@ fn analyze_variable_value(expr: &oxc::ast::ast::Expression) -> VariableValue {
 }
 
 /// Extract a list of properties from an object
+///
+/// TypeScript wrappers around the values are looked through, keeping their type annotation.
 fn extract_object_properties(
     properties: &oxc::allocator::Vec<oxc::ast::ast::ObjectPropertyKind>,
+    source_text: &str,
 ) -> Vec<ObjectProperty> {
     use oxc::ast::ast::{ObjectPropertyKind, PropertyKey};
 
@@ -536,14 +2216,17 @@ fn extract_object_properties(
                 };
 
                 // Analyze the value
+                let (expression, type_annotation) =
+                    unwrap_type_expression(&obj_prop.value, source_text);
                 let (value_pattern, value, raw) =
-                    analyze_expression_to_pattern_value_raw(&obj_prop.value);
+                    analyze_expression_to_pattern_value_raw(expression);
 
                 result.push(ObjectProperty {
                     key,
                     value_pattern,
                     value,
                     raw,
+                    type_annotation,
                 });
             }
             ObjectPropertyKind::SpreadProperty(spread) => {
@@ -565,7 +2248,8 @@ fn analyze_expression_to_pattern_value_raw(
 ) -> (String, Option<String>, String) {
     use oxc::ast::ast::Expression;
 
-    match expr {
+    let inner = expr.get_inner_expression();
+    match inner {
         Expression::StringLiteral(s) => (
             "string".to_string(),
             Some(s.value.to_string()),
@@ -614,7 +2298,18 @@ fn analyze_expression_to_pattern_value_raw(
             let raw = format!("{callee_str}()");
             ("call".to_string(), None, raw)
         }
-        _ => ("expression".to_string(), None, "<expression>".to_string()),
+        Expression::ObjectExpression(_) => ("object".to_string(), None, "{...}".to_string()),
+        Expression::ArrayExpression(_) => ("array".to_string(), None, "[...]".to_string()),
+        _ => match analyze_operator_expression(inner) {
+            Some((kind, raw)) => PropValue::Expression {
+                raw,
+                kind,
+                shape: None,
+                type_annotation: None,
+            }
+            .to_pattern_value_and_raw(),
+            None => ("expression".to_string(), None, "<expression>".to_string()),
+        },
     }
 }
 
@@ -634,6 +2329,7 @@ fn merge_variable_values(v1: VariableValue, v2: VariableValue) -> VariableValue
 fn analyze_spread_attribute(
     expr: &oxc::ast::ast::Expression,
     variables: &std::collections::HashMap<String, VariableValue>,
+    source_text: &str,
 ) -> SpreadAttribute {
     use oxc::ast::ast::Expression;
 
@@ -641,16 +2337,16 @@ fn analyze_spread_attribute(
         // Parenthesized expression: {...(expr)}
         Expression::ParenthesizedExpression(paren) => {
             // Remove parentheses and process recursively
-            analyze_spread_attribute(&paren.expression, variables)
+            analyze_spread_attribute(&paren.expression, variables, source_text)
         }
 
         // Object literal: {...{ key: value }}
         Expression::ObjectExpression(obj) => {
-            let properties = extract_object_properties(&obj.properties);
-            let resolved_props: Vec<ResolvedProp> = properties
-                .into_iter()
-                .map(|p| ResolvedProp::new(p.key, p.value_pattern, p.value, p.raw))
-                .collect();
+            let resolved_props: Vec<ResolvedProp> =
+                extract_object_properties(&obj.properties, source_text)
+                    .into_iter()
+                    .map(ObjectProperty::into_resolved)
+                    .collect();
             SpreadAttribute::new_with_resolved("<inline>".to_string(), resolved_props)
         }
 
@@ -662,14 +2358,8 @@ fn analyze_spread_attribute(
                     VariableValue::ObjectLiteral(properties) => {
                         let resolved_props: Vec<ResolvedProp> = properties
                             .iter()
-                            .map(|p| {
-                                ResolvedProp::new(
-                                    p.key.clone(),
-                                    p.value_pattern.clone(),
-                                    p.value.clone(),
-                                    p.raw.clone(),
-                                )
-                            })
+                            .cloned()
+                            .map(ObjectProperty::into_resolved)
                             .collect();
                         SpreadAttribute::new_with_resolved(var_name, resolved_props)
                     }
@@ -705,8 +2395,9 @@ fn analyze_spread_attribute(
         // Conditional expression: {...(condition ? obj1 : obj2)}
         Expression::ConditionalExpression(cond) => {
             // Analyze both branches
-            let consequent_attr = analyze_spread_attribute(&cond.consequent, variables);
-            let alternate_attr = analyze_spread_attribute(&cond.alternate, variables);
+            let consequent_attr =
+                analyze_spread_attribute(&cond.consequent, variables, source_text);
+            let alternate_attr = analyze_spread_attribute(&cond.alternate, variables, source_text);
 
             // Merge if both can be resolved
             if let (Some(props1), Some(props2)) = (
@@ -727,35 +2418,45 @@ fn analyze_spread_attribute(
     }
 }
 
-fn offset_to_line_col(source_text: &str, offset: u32) -> (u32, u32) {
-    let offset = offset as usize;
-    if offset > source_text.len() {
-        return (1, 1);
-    }
-
-    let mut line = 1u32;
-    let mut col = 1u32;
+/// Start offsets of the lines of a document, to turn byte offsets into lines and columns
+struct LineIndex<'b> {
+    source_text: &'b str,
+    line_starts: Vec<usize>,
+}
 
-    for (i, ch) in source_text.char_indices() {
-        if i >= offset {
-            break;
-        }
-        if ch == '\n' {
-            line += 1;
-            col = 1;
-        } else {
-            col += 1;
+impl<'b> LineIndex<'b> {
+    fn new(source_text: &'b str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(source_text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self {
+            source_text,
+            line_starts,
         }
     }
 
-    (line, col)
-}
+    /// 1-based line and column, counting columns in characters
+    fn line_col(&self, offset: u32) -> (u32, u32) {
+        let offset = offset as usize;
+        if offset > self.source_text.len() {
+            return (1, 1);
+        }
+
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let line_start = self.line_starts[line];
+        let col = self.source_text[line_start..]
+            .char_indices()
+            .take_while(|(i, _)| line_start + i < offset)
+            .count();
+        (line as u32 + 1, col as u32 + 1)
+    }
 
-fn create_span_with_position(source_text: &str, start: u32, end: u32) -> Span {
-    let (start_line, start_col) = offset_to_line_col(source_text, start);
-    let (end_line, end_col) = offset_to_line_col(source_text, end);
+    fn span(&self, start: u32, end: u32) -> Span {
+        let (start_line, start_col) = self.line_col(start);
+        let (end_line, end_col) = self.line_col(end);
 
-    Span::new(start, end, start_line, start_col, end_line, end_col)
+        Span::new(start, end, start_line, start_col, end_line, end_col)
+    }
 }
 
 /// Remove common leading indentation from all lines and preserve relative indentation
@@ -1038,7 +2739,295 @@ function App() {
         let parser = OxcParser::new();
         let result = parser.parse(source, &source_file).unwrap();
 
-        assert_eq!(result.jsx_elements().len(), 1);
+        assert_eq!(result.jsx_elements().len(), 1);
+    }
+
+    #[test]
+    fn test_analyze_jsx_expression_operators() {
+        let source = r#"
+function App() {
+    return (
+        <Button
+            className={`btn ${size}`}
+            label={`Save`}
+            disabled={!enabled}
+            offset={-1}
+            open={isOpen && !state.loading}
+            title={title ?? "Untitled"}
+            wide={count > 0}
+        />
+    )
+}
+"#;
+
+        let file_path = PathBuf::from("/test/App.tsx");
+        let source_file = SourceFile::new_for_test(file_path.clone(), PathBuf::from("App.tsx"));
+
+        let parser = OxcParser::new();
+        let result = parser.parse(source, &source_file).unwrap();
+
+        let props: Vec<_> = result.jsx_elements()[0]
+            .attributes()
+            .iter()
+            .filter_map(|attr| match attr {
+                crate::analyze::JSXAttribute::Regular(prop) => Some(prop.to_simplified()),
+                _ => None,
+            })
+            .map(|p| {
+                (
+                    p.key().to_string(),
+                    p.value_pattern().to_string(),
+                    p.value().map(str::to_string),
+                    p.raw().to_string(),
+                )
+            })
+            .collect();
+
+        let expected = [
+            ("className", "template", None, "`btn ${size}`"),
+            ("label", "template", Some("Save"), "`Save`"),
+            ("disabled", "unary", None, "!enabled"),
+            ("offset", "literal", Some("-1"), "-1"),
+            ("open", "logical", None, "isOpen && !state.loading"),
+            ("title", "logical", None, "title ?? \"Untitled\""),
+            ("wide", "binary", None, "count > 0"),
+        ];
+        assert_eq!(props.len(), expected.len());
+        for ((key, pattern, value, raw), actual) in expected.iter().zip(&props) {
+            assert_eq!(
+                actual,
+                &(
+                    key.to_string(),
+                    pattern.to_string(),
+                    value.map(str::to_string),
+                    raw.to_string()
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn test_expression_props_keep_source_text() {
+        let source = r#"
+function App() {
+    return (
+        <Button
+            size={big ? "lg" : small ? "sm" : size}
+            onClick={() => setOpen(true)}
+            label={format(name,   count)}
+        />
+    )
+}
+"#;
+
+        let file_path = PathBuf::from("/test/App.tsx");
+        let source_file = SourceFile::new_for_test(file_path.clone(), PathBuf::from("App.tsx"));
+
+        let parser = OxcParser::new();
+        let result = parser.parse(source, &source_file).unwrap();
+
+        let props: Vec<_> = result.jsx_elements()[0]
+            .attributes()
+            .iter()
+            .filter_map(|attr| match attr {
+                crate::analyze::JSXAttribute::Regular(prop) => Some(prop.to_simplified()),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(props[0].raw(), r#"big ? "lg" : small ? "sm" : size"#);
+        assert_eq!(
+            props[0].shape(),
+            Some("<condition> ? <consequent> : <alternate>")
+        );
+        assert_eq!(props[0].branch_values(), ["lg", "sm"]);
+
+        assert_eq!(props[1].raw(), "() => setOpen(true)");
+        assert_eq!(props[1].shape(), Some("() => {}"));
+
+        assert_eq!(props[2].raw(), "format(name, count)");
+        assert_eq!(props[2].shape(), Some("format()"));
+        assert!(props[2].branch_values().is_empty());
+    }
+
+    #[test]
+    fn test_object_and_array_props() {
+        let source = r#"
+function App() {
+    return (
+        <Box
+            style={{ margin: 0, color: theme.primary }}
+            items={["a", { id: 1 }, ...rest]}
+        />
+    )
+}
+"#;
+
+        let file_path = PathBuf::from("/test/App.tsx");
+        let source_file = SourceFile::new_for_test(file_path.clone(), PathBuf::from("App.tsx"));
+
+        let parser = OxcParser::new();
+        let result = parser.parse(source, &source_file).unwrap();
+
+        let attributes = result.jsx_elements()[0].attributes();
+        let crate::analyze::JSXAttribute::Regular(style) = &attributes[0] else {
+            panic!("style should be a regular prop");
+        };
+        let simplified = style.to_simplified();
+        assert_eq!(simplified.value_pattern(), "object");
+        assert_eq!(simplified.shape(), Some("{ margin, color }"));
+
+        let properties: Vec<_> = style
+            .to_simplified_properties()
+            .iter()
+            .map(|p| {
+                (
+                    p.key().to_string(),
+                    p.value_pattern().to_string(),
+                    p.raw().to_string(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            properties,
+            [
+                (
+                    "style.margin".to_string(),
+                    "literal".to_string(),
+                    "0".to_string()
+                ),
+                (
+                    "style.color".to_string(),
+                    "member".to_string(),
+                    "theme.primary".to_string()
+                ),
+            ]
+        );
+
+        let crate::analyze::JSXAttribute::Regular(items) = &attributes[1] else {
+            panic!("items should be a regular prop");
+        };
+        let PropValue::Expression {
+            kind: ExpressionKind::ArrayLiteral { element_kinds },
+            ..
+        } = items.value()
+        else {
+            panic!("items should be an array literal");
+        };
+        assert_eq!(element_kinds, &["string", "object", "spread"]);
+        assert_eq!(
+            items.to_simplified().shape(),
+            Some("[string, object, spread]")
+        );
+    }
+
+    #[test]
+    fn test_unwrap_type_expressions() {
+        let source = r#"
+function App() {
+    return (
+        <Button
+            variant={"primary" as const}
+            size={(size satisfies Size)}
+            buttonRef={ref!}
+            render={<T,>(item: T) => item}
+            style={{
+                className: `btn ${x}` as const,
+                hidden: (!enabled),
+                color: (a ?? b) satisfies Color,
+            }}
+        />
+    )
+}
+"#;
+
+        let file_path = PathBuf::from("/test/App.tsx");
+        let source_file = SourceFile::new_for_test(file_path.clone(), PathBuf::from("App.tsx"));
+
+        let parser = OxcParser::new();
+        let result = parser.parse(source, &source_file).unwrap();
+
+        let props: Vec<_> = result.jsx_elements()[0]
+            .attributes()
+            .iter()
+            .filter_map(|attr| match attr {
+                crate::analyze::JSXAttribute::Regular(prop) => Some(prop.to_simplified()),
+                _ => None,
+            })
+            .map(|p| {
+                (
+                    p.value_pattern().to_string(),
+                    p.value().map(str::to_string),
+                    p.raw().to_string(),
+                    p.type_annotation().map(str::to_string),
+                )
+            })
+            .collect();
+
+        let expected = [
+            ("literal", Some("primary"), "primary", Some("as const")),
+            ("identifier", None, "size", Some("satisfies Size")),
+            ("identifier", None, "ref", Some("!")),
+            ("arrow", None, "<T,>(item: T) => item", None),
+            (
+                "object",
+                None,
+                "{ className: `btn ${x}` as const, hidden: (!enabled), color: (a ?? b) satisfies Color, }",
+                None,
+            ),
+        ];
+        assert_eq!(props.len(), expected.len());
+        for ((pattern, value, raw, annotation), actual) in expected.iter().zip(&props) {
+            assert_eq!(
+                actual,
+                &(
+                    pattern.to_string(),
+                    value.map(str::to_string),
+                    raw.to_string(),
+                    annotation.map(str::to_string)
+                )
+            );
+        }
+
+        // Object properties are unwrapped the same way
+        let crate::analyze::JSXAttribute::Regular(style) =
+            &result.jsx_elements()[0].attributes()[4]
+        else {
+            panic!("style should be a regular prop");
+        };
+        let properties: Vec<_> = style
+            .to_simplified_properties()
+            .iter()
+            .map(|p| {
+                (
+                    p.key().to_string(),
+                    p.value_pattern().to_string(),
+                    p.raw().to_string(),
+                    p.type_annotation().map(str::to_string),
+                )
+            })
+            .collect();
+        let owned = |key: &str, pattern: &str, raw: &str, annotation: Option<&str>| {
+            (
+                key.to_string(),
+                pattern.to_string(),
+                raw.to_string(),
+                annotation.map(str::to_string),
+            )
+        };
+        assert_eq!(
+            properties,
+            [
+                owned(
+                    "style.className",
+                    "template",
+                    "`btn ${x}`",
+                    Some("as const")
+                ),
+                owned("style.hidden", "unary", "!enabled", None),
+                owned("style.color", "logical", "a ?? b", Some("satisfies Color")),
+            ]
+        );
     }
 
     #[test]
@@ -1312,7 +3301,7 @@ function App() {
         if let crate::analyze::ChildNode::Expression { kind, .. } = &button.children()[0] {
             assert!(matches!(
                 kind,
-                crate::analyze::ExpressionKind::ConditionalExpression
+                crate::analyze::ExpressionKind::ConditionalExpression { .. }
             ));
         } else {
             panic!("Expected conditional expression child node");
@@ -1694,7 +3683,580 @@ function App() {
             "ThreeStepModal should be collected but was not found"
         );
     }
+
+    #[test]
+    fn test_extract_require_bindings() {
+        let source = r#"
+const { Button, Dialog: Modal } = require("ui");
+const Icons = require("./icons");
+const Card = require("./Card").default;
+
+function App() {
+    return <Button />
+}
+"#;
+
+        let file_path = PathBuf::from("/test/App.jsx");
+        let source_file = SourceFile::new_for_test(file_path.clone(), PathBuf::from("App.jsx"));
+
+        let parser = OxcParser::new();
+        let result = parser.parse(source, &source_file).unwrap();
+
+        let find = |name: &str| {
+            result
+                .imports()
+                .iter()
+                .find(|i| i.local_name() == name)
+                .unwrap_or_else(|| panic!("{name} binding should be collected"))
+        };
+
+        assert_eq!(find("Button").source().as_str(), "ui");
+        assert_eq!(
+            find("Button").imported_name(),
+            &ImportedName::Named("Button".to_string())
+        );
+        assert_eq!(
+            find("Modal").imported_name(),
+            &ImportedName::Named("Dialog".to_string())
+        );
+        assert_eq!(find("Icons").imported_name(), &ImportedName::Namespace);
+        assert_eq!(find("Card").imported_name(), &ImportedName::Default);
+    }
+
+    #[test]
+    fn test_extract_lazy_bindings() {
+        let source = r#"
+import React, { lazy } from "react";
+import dynamic from "next/dynamic";
+
+const Modal = React.lazy(() => import("./Modal"));
+const Drawer = lazy(() => import("./Drawer").then((m) => ({ default: m.Drawer })));
+const Chart = dynamic(() => import("./Chart").then((mod) => mod.Chart), { ssr: false });
+
+function App() {
+    return <Modal />
+}
+"#;
+
+        let file_path = PathBuf::from("/test/App.tsx");
+        let source_file = SourceFile::new_for_test(file_path.clone(), PathBuf::from("App.tsx"));
+
+        let parser = OxcParser::new();
+        let result = parser.parse(source, &source_file).unwrap();
+
+        let find = |name: &str| {
+            result
+                .imports()
+                .iter()
+                .find(|i| i.local_name() == name)
+                .unwrap_or_else(|| panic!("{name} binding should be collected"))
+        };
+
+        assert_eq!(find("Modal").source().as_str(), "./Modal");
+        assert_eq!(
+            find("Modal").imported_name(),
+            &ImportedName::Lazy("default".to_string())
+        );
+        assert_eq!(
+            find("Drawer").imported_name(),
+            &ImportedName::Lazy("Drawer".to_string())
+        );
+        assert_eq!(find("Chart").source().as_str(), "./Chart");
+        assert_eq!(
+            find("Chart").imported_name(),
+            &ImportedName::Lazy("Chart".to_string())
+        );
+    }
+
+    #[test]
+    fn test_lazy_loaders_must_be_imported() {
+        let source = r#"
+import { lazy } from "./utils";
+
+const Modal = lazy(() => import("./Modal"));
+const Chart = dynamic(() => import("./Chart"));
+const Drawer = Loader.lazy(() => import("./Drawer"));
+"#;
+
+        let file_path = PathBuf::from("/test/App.tsx");
+        let source_file = SourceFile::new_for_test(file_path.clone(), PathBuf::from("App.tsx"));
+
+        let result = OxcParser::new().parse(source, &source_file).unwrap();
+        assert!(
+            result
+                .imports()
+                .iter()
+                .all(|binding| !binding.imported_name().is_lazy())
+        );
+    }
+
+    #[test]
+    fn test_resolve_namespace_aliases() {
+        let source = r#"
+import * as UI from "ui";
+import { Menu } from "./menu";
+
+const { Button, Dialog: Modal } = UI;
+const Btn = UI.Forms.Button;
+const Primary = Btn;
+const { Item } = Menu;
+const notAnImport = other.value;
+
+function App() {
+    return <Button />
+}
+"#;
+
+        let file_path = PathBuf::from("/test/App.tsx");
+        let source_file = SourceFile::new_for_test(file_path.clone(), PathBuf::from("App.tsx"));
+
+        let parser = OxcParser::new();
+        let result = parser.parse(source, &source_file).unwrap();
+
+        let find = |name: &str| {
+            result
+                .aliases()
+                .find(|a| a.local_name() == name)
+                .unwrap_or_else(|| panic!("{name} alias should be resolved"))
+        };
+
+        assert_eq!(find("Button").source().as_str(), "ui");
+        assert_eq!(find("Button").imported_name(), &ImportedName::Namespace);
+        assert_eq!(find("Button").member_path(), ["Button"]);
+        assert_eq!(find("Modal").member_path(), ["Dialog"]);
+        assert_eq!(find("Btn").member_path(), ["Forms", "Button"]);
+        assert_eq!(find("Primary").member_path(), ["Forms", "Button"]);
+        assert_eq!(
+            find("Item").imported_name(),
+            &ImportedName::Named("Menu".to_string())
+        );
+        assert_eq!(find("Item").member_path(), ["Item"]);
+        assert!(result.aliases().all(|a| a.local_name() != "notAnImport"));
+
+        let button = &result.jsx_elements()[0];
+        let binding = result.find_binding_for_element(button).unwrap();
+        assert_eq!(binding.member_path(), ["Button"]);
+    }
+
+    #[test]
+    fn test_aliases_are_scoped_to_their_function() {
+        let source = r#"
+import * as UI from "ui";
+
+function Toolbar() {
+    const { Button } = UI;
+    return <Button />;
+}
+
+function Form() {
+    const Button = (props) => <button {...props} />;
+    return <Button />;
+}
+
+const Page = () => {
+    const { Dialog } = UI;
+    const Content = () => <Dialog />;
+    return <Content />;
+};
+"#;
+
+        let file_path = PathBuf::from("/test/App.tsx");
+        let source_file = SourceFile::new_for_test(file_path.clone(), PathBuf::from("App.tsx"));
+
+        let parser = OxcParser::new();
+        let result = parser.parse(source, &source_file).unwrap();
+
+        let binding = |name: &str, line: u32| {
+            let element = result
+                .jsx_elements()
+                .iter()
+                .find(|e| {
+                    e.tag_name().get_identifier() == name
+                        && e.location().span().start_line() == line
+                })
+                .unwrap();
+            result
+                .find_binding_for_element(element)
+                .map(|b| b.member_path().to_vec())
+        };
+
+        assert_eq!(binding("Button", 6), Some(vec!["Button".to_string()]));
+        // `Form` declares its own `Button`, which the alias in `Toolbar` must not shadow
+        assert_eq!(binding("Button", 11), None);
+        // Nested functions see the aliases of the functions enclosing them
+        assert_eq!(binding("Dialog", 16), Some(vec!["Dialog".to_string()]));
+    }
+
+    #[test]
+    fn test_parse_nested_member_expression() {
+        let source = r#"
+function App() {
+    return <Menu.Item.Icon />
+}
+"#;
+
+        let file_path = PathBuf::from("/test/App.tsx");
+        let source_file = SourceFile::new_for_test(file_path.clone(), PathBuf::from("App.tsx"));
+
+        let parser = OxcParser::new();
+        let result = parser.parse(source, &source_file).unwrap();
+
+        assert_eq!(result.jsx_elements().len(), 1);
+        assert_eq!(
+            result.jsx_elements()[0].tag_name(),
+            &JSXElementReference::member("Menu", vec!["Item".to_string(), "Icon".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_line_index() {
+        let lines = LineIndex::new("é <A />\n\n  <B />");
+        assert_eq!(lines.line_col(0), (1, 1));
+        // Columns count characters, not bytes
+        assert_eq!(lines.line_col(3), (1, 3));
+        assert_eq!(lines.line_col(9), (2, 1));
+        assert_eq!(lines.line_col(12), (3, 3));
+        assert_eq!(lines.line_col(100), (1, 1));
+    }
+
+    #[test]
+    fn test_parse_mdx() {
+        let source = r#"---
+title: Buttons
+---
+import { Button } from "ui";
+export const Hero = () => <Button size="lg" />;
+
+# Buttons
+
+Use é <Button variant="primary">Save</Button> for the main action.
+
+```jsx
+<Button>Not a usage</Button>
+```
+
+<Card>
+  It's a card with {items.length} items.
+
+  <Button disabled />
+</Card>
+"#;
+
+        let file_path = PathBuf::from("/test/docs.mdx");
+        let source_file = SourceFile::new_for_test(file_path.clone(), PathBuf::from("docs.mdx"));
+
+        let result = OxcParser::new().parse(source, &source_file).unwrap();
+        assert!(result.diagnostics().is_empty());
+        assert_eq!(result.imports().len(), 1);
+
+        let elements = result.jsx_elements();
+        let tags: Vec<String> = elements
+            .iter()
+            .map(|e| e.tag_name().display_name())
+            .collect();
+        assert_eq!(tags, vec!["Button", "Button", "Card", "Button"]);
+        // Elements of JSX blocks are matched against the imports of the ESM block
+        assert!(result.find_binding_for_element(&elements[3]).is_some());
+        assert!(result.find_binding_for_element(&elements[2]).is_none());
+
+        let inline = elements[1].location().span();
+        assert_eq!((inline.start_line(), inline.start_col()), (9, 7));
+        assert_eq!(
+            &source[inline.start() as usize..inline.end() as usize],
+            r#"<Button variant="primary">Save</Button>"#
+        );
+        let nested = elements[3].location().span();
+        assert_eq!((nested.start_line(), nested.start_col()), (18, 3));
+    }
+
+    #[test]
+    fn test_parse_astro_component() {
+        let source = r#"---
+import { Button } from "ui";
+import Card from "../components/Card.tsx";
+const items = ["a", "b"];
+---
+<Card title="Items" client:load>
+  <ul>
+    {items.map((item) => <li><Button variant="link">{item}</Button></li>)}
+  </ul>
+  <Button disabled {...rest} size={size}>Save</Button>
+</Card>
+"#;
+
+        let file_path = PathBuf::from("/test/Page.astro");
+        let source_file = SourceFile::new_for_test(file_path.clone(), PathBuf::from("Page.astro"));
+
+        let result = OxcParser::new().parse(source, &source_file).unwrap();
+        assert!(result.diagnostics().is_empty());
+        assert_eq!(result.imports().len(), 2);
+
+        let elements = result.jsx_elements();
+        let tags: Vec<String> = elements
+            .iter()
+            .map(|e| e.tag_name().display_name())
+            .collect();
+        assert_eq!(tags, vec!["Card", "ul", "li", "Button", "Button"]);
+        assert!(
+            elements
+                .iter()
+                .filter(|e| !e.tag_name().is_native())
+                .all(|e| result.find_binding_for_element(e).is_some())
+        );
+
+        let card = &elements[0];
+        assert_eq!(card.location().span().start_line(), 6);
+        assert!(matches!(
+            &card.attributes()[1],
+            JSXAttribute::Regular(prop) if prop.name().as_string() == "client:load"
+        ));
+
+        // Expressions are parsed on their own and shifted back into the document
+        let item = &elements[2];
+        assert_eq!(
+            (
+                item.location().span().start_line(),
+                item.location().span().start_col()
+            ),
+            (8, 26)
+        );
+        assert!(item.context().in_loop);
+        assert!(item.raw_text().starts_with("<li><Button"));
+
+        let button = &elements[4];
+        assert_eq!(
+            (
+                button.location().span().start_line(),
+                button.location().span().start_col()
+            ),
+            (10, 3)
+        );
+        assert!(matches!(button.attributes()[1], JSXAttribute::Spread(_)));
+        assert!(matches!(
+            &button.attributes()[2],
+            JSXAttribute::Regular(prop) if prop.value().to_pattern_value_and_raw().0 == "identifier"
+        ));
+        assert!(matches!(button.children(), [ChildNode::Text(text)] if text == "Save"));
+    }
+
+    #[test]
+    fn test_parse_vue_component() {
+        let source = r#"<template>
+  <my-button :variant="primary ? 'primary' : 'secondary'" size="lg" @click.prevent="save" v-if="ok">
+    {{ label }}
+  </my-button>
+  <template v-for="item in items"><Icon :name="item" /></template>
+</template>
+
+<script setup lang="ts">
+import MyButton from "./MyButton.tsx";
+import { Icon } from "ui";
+</script>
+"#;
+
+        let file_path = PathBuf::from("/test/Page.vue");
+        let source_file = SourceFile::new_for_test(file_path.clone(), PathBuf::from("Page.vue"));
+
+        let result = OxcParser::new().parse(source, &source_file).unwrap();
+        let elements = result.jsx_elements();
+        let tags: Vec<String> = elements
+            .iter()
+            .map(|e| e.tag_name().display_name())
+            .collect();
+        assert_eq!(tags, vec!["MyButton", "Icon"]);
+        assert!(
+            elements
+                .iter()
+                .all(|e| result.find_binding_for_element(e).is_some())
+        );
+
+        let button = &elements[0];
+        assert_eq!(button.location().span().start_line(), 2);
+        let props: Vec<(String, String)> = button
+            .attributes()
+            .iter()
+            .filter_map(|attr| match attr {
+                JSXAttribute::Regular(prop) => Some((
+                    prop.name().as_string(),
+                    prop.value().to_pattern_value_and_raw().0,
+                )),
+                JSXAttribute::Spread(_) => None,
+            })
+            .collect();
+        assert_eq!(
+            props,
+            vec![
+                ("variant".to_string(), "conditional".to_string()),
+                ("size".to_string(), "string".to_string()),
+                ("onClick".to_string(), "identifier".to_string()),
+            ]
+        );
+        assert!(matches!(
+            button.children(),
+            [ChildNode::Expression { raw, .. }] if raw == "label"
+        ));
+
+        // The file is the component
+        assert!(button.context().conditional && !button.context().in_loop);
+        assert!(elements[1].context().in_loop && !elements[1].context().conditional);
+        assert_eq!(button.context().component.as_deref(), Some("Page"));
+    }
+
+    #[test]
+    fn test_collect_create_element_calls() {
+        let source = r#"
+import React from "react";
+import { jsx as _jsx, jsxs as _jsxs } from "react/jsx-runtime";
+import { Button, UI } from "ui";
+
+export const Legacy = () =>
+    React.createElement(Button, { variant: "primary", size, ...rest }, "Save", React.createElement(Icon, null));
+
+export const Compiled = () =>
+    _jsxs(UI.Card, { children: [_jsx(Button, { disabled: true, children: "Go" }), "Text"] }, "card");
+
+export const Renderer = ({ type }) => (0, _jsx)(type, {});
+"#;
+
+        let file_path = PathBuf::from("/test/App.js");
+        let source_file = SourceFile::new_for_test(file_path.clone(), PathBuf::from("App.js"));
+
+        let result = OxcParser::new().parse(source, &source_file).unwrap();
+        let tags: Vec<String> = result
+            .jsx_elements()
+            .iter()
+            .map(|e| e.tag_name().display_name())
+            .collect();
+        assert_eq!(tags, vec!["Button", "Icon", "UI.Card", "Button"]);
+
+        let legacy = &result.jsx_elements()[0];
+        let attributes = legacy.attributes();
+        assert_eq!(attributes.len(), 3);
+        assert!(matches!(
+            &attributes[0],
+            JSXAttribute::Regular(prop)
+                if prop.name().as_string() == "variant"
+                    && matches!(prop.value(), PropValue::StringLiteral(v) if v == "primary")
+        ));
+        assert!(matches!(
+            &attributes[1],
+            JSXAttribute::Regular(prop) if prop.value().to_pattern_value_and_raw().0 == "identifier"
+        ));
+        assert!(matches!(attributes[2], JSXAttribute::Spread(_)));
+        assert!(matches!(&legacy.children()[0], ChildNode::Text(text) if text == "Save"));
+        assert_eq!(legacy.children()[1].node_type(), "jsx");
+        assert_eq!(legacy.location().span().start_line(), 7);
+
+        // Children passed as a prop are not attributes, the key argument is ignored
+        let card = &result.jsx_elements()[2];
+        assert!(card.attributes().is_empty());
+        assert_eq!(card.children().len(), 2);
+        let button = &result.jsx_elements()[3];
+        assert_eq!(button.attributes().len(), 1);
+        assert!(matches!(button.children(), [ChildNode::Text(text)] if text == "Go"));
+
+        let disabled = OxcParser::new()
+            .with_create_element_calls(false)
+            .parse(source, &source_file)
+            .unwrap();
+        assert!(disabled.jsx_elements().is_empty());
+    }
+
+    #[test]
+    fn test_create_element_calls_must_come_from_react() {
+        let source = r#"
+import { jsx } from "./markup";
+const { createElement: h } = require("react");
+
+export function draw() {
+    const div = document.createElement("div");
+    document.body.appendChild(jsx(Panel, { title: "Hi" }));
+    return h(Button, null);
+}
+"#;
+
+        let file_path = PathBuf::from("/test/draw.js");
+        let source_file = SourceFile::new_for_test(file_path.clone(), PathBuf::from("draw.js"));
+
+        let result = OxcParser::new().parse(source, &source_file).unwrap();
+        let tags: Vec<String> = result
+            .jsx_elements()
+            .iter()
+            .map(|e| e.tag_name().display_name())
+            .collect();
+        assert_eq!(tags, vec!["Button"]);
+    }
+
+    #[test]
+    fn test_render_context() {
+        let source = r#"
+import React from "react";
+import { Icon, List, Modal, Row, Table } from "ui";
+
+export function Page({ items, open }) {
+    const renderEmpty = () => <Empty />;
+    return (
+        <List icon={<Icon />} renderEmpty={renderEmpty}>
+            {items.map((item) => <Row key={item.id} />)}
+            {open && <Modal />}
+            {open ? <Modal /> : null}
+            {(row) => <Row />}
+        </List>
+    );
+}
+
+export const Grid = () =>
+    React.createElement(Table, { render: () => React.createElement(Row) });
+"#;
+
+        let file_path = PathBuf::from("/test/Page.jsx");
+        let source_file = SourceFile::new_for_test(file_path.clone(), PathBuf::from("Page.jsx"));
+
+        let result = OxcParser::new().parse(source, &source_file).unwrap();
+        let contexts: Vec<(String, &RenderContext)> = result
+            .jsx_elements()
+            .iter()
+            .map(|e| (e.tag_name().display_name(), e.context()))
+            .collect();
+        let tags: Vec<&str> = contexts.iter().map(|(tag, _)| tag.as_str()).collect();
+        assert_eq!(
+            tags,
+            vec![
+                "Empty", "List", "Icon", "Row", "Modal", "Modal", "Row", "Table", "Row"
+            ]
+        );
+
+        let flags = |context: &RenderContext| {
+            (
+                context.in_loop,
+                context.conditional,
+                context.in_render_prop,
+                context.in_prop,
+            )
+        };
+        assert_eq!(flags(contexts[1].1), (false, false, false, false));
+        assert_eq!(flags(contexts[2].1), (false, false, false, true));
+        assert_eq!(flags(contexts[3].1), (true, false, false, false));
+        assert_eq!(flags(contexts[4].1), (false, true, false, false));
+        assert_eq!(flags(contexts[5].1), (false, true, false, false));
+        assert_eq!(flags(contexts[6].1), (false, false, true, false));
+        assert_eq!(flags(contexts[7].1), (false, false, false, false));
+        assert_eq!(flags(contexts[8].1), (false, false, true, false));
+
+        // Helpers inside a component count as the component
+        assert!(
+            contexts[..7]
+                .iter()
+                .all(|(_, context)| context.component.as_deref() == Some("Page"))
+        );
+        assert!(
+            contexts[7..]
+                .iter()
+                .all(|(_, context)| context.component.as_deref() == Some("Grid"))
+        );
+    }
 }
+use schemars::JsonSchema;
 use serde::Serialize;
 use std::path::{Path, PathBuf};
 
@@ -1762,7 +4324,7 @@ impl SourceLocation {
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct Span {
     start: u32,
     end: u32,
@@ -1820,6 +4382,8 @@ pub struct ImportBinding {
     source: ModuleSpecifier,
     imported_name: ImportedName,
     local_name: String,
+    /// Members accessed on the import before reaching the local name (`const { Button } = UI`)
+    member_path: Vec<String>,
 }
 
 impl ImportBinding {
@@ -1828,6 +4392,19 @@ impl ImportBinding {
             source,
             imported_name,
             local_name,
+            member_path: Vec::new(),
+        }
+    }
+
+    /// Create a binding for a local variable derived from this one
+    pub fn derive(&self, local_name: String, members: &[String]) -> Self {
+        let mut member_path = self.member_path.clone();
+        member_path.extend(members.iter().cloned());
+        Self {
+            source: self.source.clone(),
+            imported_name: self.imported_name.clone(),
+            local_name,
+            member_path,
         }
     }
 
@@ -1842,6 +4419,10 @@ impl ImportBinding {
     pub fn local_name(&self) -> &str {
         &self.local_name
     }
+
+    pub fn member_path(&self) -> &[String] {
+        &self.member_path
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -1870,4 +4451,12 @@ pub enum ImportedName {
     Named(String),
     Default,
     Namespace,
+    /// Component loaded through `lazy(() => import(...))` or `dynamic(...)`, holding the export name
+    Lazy(String),
+}
+
+impl ImportedName {
+    pub fn is_lazy(&self) -> bool {
+        matches!(self, Self::Lazy(_))
+    }
 }
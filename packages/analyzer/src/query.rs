@@ -0,0 +1,408 @@
+//! Query language over component usages
+//!
+//! ```text
+//! query    = selector { "," selector }
+//! selector = [ name ] { filter } { "from" package | "in" glob }
+//! filter   = "[" key "]"              prop is passed
+//!          | "[!" key "]"             prop is not passed
+//!          | "[" key "=" value "]"    prop has the value
+//!          | "[" key "!=" value "]"   prop is passed with another value
+//!          | "[" key ":" pattern "]"  prop value has the pattern (string, identifier, ...)
+//!          | ":internal" | ":external" | ":native"
+//! ```
+//!
+//! `name` and `package` accept a trailing `*`, `glob` is matched against the project-relative
+//! file path and children are matched as the `children` prop, e.g.
+//! `Button[variant="primary"][!size] in src/pages/**` or `*[!children] from @acme/*`.
+
+use globset::{GlobBuilder, GlobMatcher};
+
+use crate::analyze::{ComponentSource, ComponentUsage, ComponentUsageAggregate, SimplifiedProp};
+use crate::check::ComponentSelector;
+use crate::rules::prop_matches;
+use crate::{AnalysisError, Result};
+
+/// A parsed query, matching usages that match any of its selectors
+#[derive(Debug, Clone)]
+pub struct Query {
+    selectors: Vec<Selector>,
+}
+
+#[derive(Debug, Clone)]
+struct Selector {
+    component: ComponentSelector,
+    source: Option<SourceType>,
+    props: Vec<PropFilter>,
+    files: Option<GlobMatcher>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SourceType {
+    Internal,
+    External,
+    Native,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PropFilter {
+    Present(String),
+    Absent(String),
+    Equals(String, String),
+    NotEquals(String, String),
+    Pattern(String, String),
+}
+
+impl Query {
+    pub fn parse(query: &str) -> Result<Self> {
+        QueryParser::new(query).parse()
+    }
+
+    /// Whether the usage of the component matches the query
+    pub fn matches(&self, aggregate: &ComponentUsageAggregate, usage: &ComponentUsage) -> bool {
+        self.selectors
+            .iter()
+            .any(|s| s.matches_component(aggregate) && s.matches_usage(usage))
+    }
+
+    /// Components with at least one matching usage, keeping only the matching usages
+    pub fn filter(&self, components: &[ComponentUsageAggregate]) -> Vec<ComponentUsageAggregate> {
+        components
+            .iter()
+            .filter_map(|aggregate| {
+                let selectors: Vec<&Selector> = self
+                    .selectors
+                    .iter()
+                    .filter(|s| s.matches_component(aggregate))
+                    .collect();
+                if selectors.is_empty() {
+                    return None;
+                }
+
+                let mut aggregate = aggregate.clone();
+                aggregate.retain_usages(|usage| selectors.iter().any(|s| s.matches_usage(usage)));
+                (!aggregate.usages().is_empty()).then_some(aggregate)
+            })
+            .collect()
+    }
+}
+
+impl Selector {
+    fn matches_component(&self, aggregate: &ComponentUsageAggregate) -> bool {
+        let source = match aggregate.identity().source() {
+            ComponentSource::Internal { .. } => SourceType::Internal,
+            ComponentSource::External { .. } => SourceType::External,
+            ComponentSource::Native => SourceType::Native,
+        };
+        self.source.is_none_or(|s| s == source) && self.component.matches(aggregate)
+    }
+
+    fn matches_usage(&self, usage: &ComponentUsage) -> bool {
+        let in_files = self.files.as_ref().is_none_or(|glob| {
+            let path = usage.occurrence().location().file().display_path();
+            glob.is_match(path.replace('\\', "/"))
+        });
+        in_files
+            && self
+                .props
+                .iter()
+                .all(|f| f.matches(usage.simplified_props()))
+    }
+}
+
+impl PropFilter {
+    fn matches(&self, props: &[SimplifiedProp]) -> bool {
+        match self {
+            Self::Present(key) => props.iter().any(|p| p.key() == key),
+            Self::Absent(key) => !props.iter().any(|p| p.key() == key),
+            Self::Equals(key, value) => props.iter().any(|p| prop_matches(p, key, Some(value))),
+            Self::NotEquals(key, value) => props
+                .iter()
+                .any(|p| p.key() == key && !prop_matches(p, key, Some(value))),
+            Self::Pattern(key, pattern) => props
+                .iter()
+                .any(|p| p.key() == key && p.value_pattern() == pattern),
+        }
+    }
+}
+
+struct QueryParser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> QueryParser<'a> {
+    fn new(input: &'a str) -> Self {
+        Self { input, pos: 0 }
+    }
+
+    fn parse(mut self) -> Result<Query> {
+        let mut selectors = vec![self.selector()?];
+        while self.eat(',') {
+            selectors.push(self.selector()?);
+        }
+        self.skip_whitespace();
+        if let Some(c) = self.peek() {
+            return Err(self.error(&format!("unexpected `{c}`")));
+        }
+        Ok(Query { selectors })
+    }
+
+    fn selector(&mut self) -> Result<Selector> {
+        self.skip_whitespace();
+        let name = self.take_while(|c| c.is_alphanumeric() || "_$.-*".contains(c));
+        let mut selector = Selector {
+            component: ComponentSelector {
+                name: if name.is_empty() { "*" } else { name }.to_string(),
+                ..Default::default()
+            },
+            source: None,
+            props: Vec::new(),
+            files: None,
+        };
+
+        loop {
+            if self.eat('[') {
+                selector.props.push(self.prop_filter()?);
+            } else if self.eat(':') {
+                selector.source = Some(match self.word() {
+                    "internal" => SourceType::Internal,
+                    "external" => SourceType::External,
+                    "native" => SourceType::Native,
+                    other => {
+                        return Err(self.error(&format!("unknown source type `{other}`")));
+                    }
+                });
+            } else {
+                break;
+            }
+        }
+
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                None | Some(',') => break,
+                _ => {}
+            }
+            match self.word() {
+                "from" => selector.component.package = Some(self.argument()?),
+                "in" => {
+                    let pattern = self.argument()?;
+                    let glob = GlobBuilder::new(&pattern)
+                        .literal_separator(true)
+                        .build()
+                        .map_err(|e| self.error(&format!("invalid glob `{pattern}`: {e}")))?;
+                    selector.files = Some(glob.compile_matcher());
+                }
+                other => {
+                    return Err(self.error(&format!("expected `from` or `in`, found `{other}`")));
+                }
+            }
+        }
+
+        Ok(selector)
+    }
+
+    /// Everything after `[`, up to and including `]`
+    fn prop_filter(&mut self) -> Result<PropFilter> {
+        self.skip_whitespace();
+        let negated = self.eat('!');
+        let key = self
+            .take_while(|c| !c.is_whitespace() && !"]=!:".contains(c))
+            .to_string();
+        if key.is_empty() {
+            return Err(self.error("expected a prop name"));
+        }
+        self.skip_whitespace();
+
+        let filter = if negated {
+            PropFilter::Absent(key)
+        } else if self.eat('=') {
+            PropFilter::Equals(key, self.value()?)
+        } else if self.input[self.pos..].starts_with("!=") {
+            self.pos += 2;
+            PropFilter::NotEquals(key, self.value()?)
+        } else if self.eat(':') {
+            self.skip_whitespace();
+            PropFilter::Pattern(key, self.word().to_string())
+        } else {
+            PropFilter::Present(key)
+        };
+
+        self.skip_whitespace();
+        if !self.eat(']') {
+            return Err(self.error("expected `]`"));
+        }
+        Ok(filter)
+    }
+
+    fn value(&mut self) -> Result<String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(quote @ ('"' | '\'')) => self.quoted(quote),
+            _ => Ok(self.take_while(|c| c != ']').trim_end().to_string()),
+        }
+    }
+
+    /// Package name or glob, optionally quoted
+    fn argument(&mut self) -> Result<String> {
+        self.skip_whitespace();
+        let argument = match self.peek() {
+            Some(quote @ ('"' | '\'')) => self.quoted(quote)?,
+            _ => self
+                .take_while(|c| !c.is_whitespace() && c != ',')
+                .to_string(),
+        };
+        if argument.is_empty() {
+            return Err(self.error("expected a pattern"));
+        }
+        Ok(argument)
+    }
+
+    fn quoted(&mut self, quote: char) -> Result<String> {
+        self.pos += quote.len_utf8();
+        let mut value = String::new();
+        let mut chars = self.input[self.pos..].char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '\\' => {
+                    if let Some((_, escaped)) = chars.next() {
+                        value.push(escaped);
+                    }
+                }
+                c if c == quote => {
+                    self.pos += i + c.len_utf8();
+                    return Ok(value);
+                }
+                c => value.push(c),
+            }
+        }
+        Err(self.error("unterminated string"))
+    }
+
+    fn word(&mut self) -> &'a str {
+        self.take_while(|c| c.is_alphanumeric() || c == '_' || c == '-')
+    }
+
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> &'a str {
+        let rest = &self.input[self.pos..];
+        let len = rest.find(|c| !f(c)).unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.take_while(char::is_whitespace);
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..].chars().next()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn error(&self, message: &str) -> AnalysisError {
+        AnalysisError::new(format!("Invalid query at position {}: {message}", self.pos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TestProject;
+
+    #[test]
+    fn test_parse_query() {
+        let query = Query::parse(r#"Button[variant="primary"][!size] in src/pages/**"#).unwrap();
+        let selector = &query.selectors[0];
+        assert_eq!(selector.component.name, "Button");
+        assert_eq!(
+            selector.props,
+            vec![
+                PropFilter::Equals("variant".to_string(), "primary".to_string()),
+                PropFilter::Absent("size".to_string()),
+            ]
+        );
+        assert!(
+            selector
+                .files
+                .as_ref()
+                .unwrap()
+                .is_match("src/pages/a/b.tsx")
+        );
+        assert!(
+            !selector
+                .files
+                .as_ref()
+                .unwrap()
+                .is_match("src/components/a.tsx")
+        );
+
+        let query = Query::parse("Menu.*:external[onClick:arrow] from @acme/*, div").unwrap();
+        assert_eq!(query.selectors.len(), 2);
+        assert_eq!(query.selectors[0].source, Some(SourceType::External));
+        assert_eq!(
+            query.selectors[0].component.package.as_deref(),
+            Some("@acme/*")
+        );
+        assert_eq!(query.selectors[1].component.name, "div");
+
+        assert!(Query::parse("Button[variant").is_err());
+        assert!(Query::parse("Button[size='lg]").is_err());
+        assert!(Query::parse("Button within src").is_err());
+    }
+
+    #[test]
+    fn test_filter_usages() {
+        let project = TestProject::new(&[
+            (
+                "src/Button.tsx",
+                "export const Button = (props) => <button {...props} />;",
+            ),
+            (
+                "src/pages/Home.tsx",
+                r#"import { Button } from "../Button";
+export const Home = () => (
+  <>
+    <Button variant="primary" />
+    <Button variant="primary" size="lg" />
+    <Button variant="secondary">Save</Button>
+  </>
+);"#,
+            ),
+            (
+                "src/App.tsx",
+                r#"import { Button } from "./Button";
+export const App = () => <Button variant="primary" />;"#,
+            ),
+        ]);
+        let report = crate::AnalysisService::default()
+            .run(project.root())
+            .unwrap();
+        let count = |query: &str| -> usize {
+            Query::parse(query)
+                .unwrap()
+                .filter(report.components())
+                .iter()
+                .map(|a| a.usages().len())
+                .sum()
+        };
+
+        assert_eq!(
+            count(r#"Button[variant="primary"][!size] in src/pages/**"#),
+            1
+        );
+        assert_eq!(count(r#"Button[variant="primary"]"#), 3);
+        assert_eq!(count(r#"Button[variant!="primary"]"#), 1);
+        assert_eq!(count("Button[children]"), 1);
+        assert_eq!(count("Button:internal[size:string]"), 1);
+        assert_eq!(count("Button:native"), 0);
+        assert_eq!(count("button[!children], Button in src/App.tsx"), 2);
+    }
+}
@@ -130,6 +130,13 @@ pub struct ResolvedModule {
 }
 
 impl ResolvedModule {
+    pub fn new(canonical_path: PathBuf, package_info: Option<Package>) -> Self {
+        Self {
+            canonical_path,
+            package_info,
+        }
+    }
+
     pub fn canonical_path(&self) -> &Path {
         &self.canonical_path
     }
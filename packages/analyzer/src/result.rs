@@ -1,13 +1,21 @@
+use schemars::JsonSchema;
 use serde::Serialize;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use crate::AnalysisError;
 use crate::analyze::ComponentUsageAggregate;
+use crate::config::AnalyzerConfig;
+use crate::diagnostic::Diagnostic;
+use crate::rules::RuleViolation;
+use crate::schema::SCHEMA_VERSION;
+use crate::service::Package;
 
 #[derive(Debug)]
 pub enum AnalysisResult {
     /// Analysis succeeded
-    Success { report: AnalysisReport },
+    Success { report: Box<AnalysisReport> },
     /// No files found
     NoFilesFound,
     /// Invalid path
@@ -43,7 +51,9 @@ impl AnalysisResult {
 impl From<Result<AnalysisReport, AnalysisError>> for AnalysisResult {
     fn from(result: Result<AnalysisReport, AnalysisError>) -> Self {
         match result {
-            Ok(report) => Self::Success { report },
+            Ok(report) => Self::Success {
+                report: Box::new(report),
+            },
             Err(err) => {
                 if err.message().contains("No target files") {
                     Self::NoFilesFound
@@ -57,10 +67,14 @@ impl From<Result<AnalysisReport, AnalysisError>> for AnalysisResult {
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct AnalysisReport {
     meta: AnalysisMetadata,
     components: Vec<ComponentUsageAggregate>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    violations: Vec<RuleViolation>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    diagnostics: Vec<Diagnostic>,
 }
 
 impl AnalysisReport {
@@ -68,9 +82,16 @@ impl AnalysisReport {
         Self {
             meta,
             components: aggregates,
+            violations: Vec::new(),
+            diagnostics: Vec::new(),
         }
     }
 
+    pub fn with_violations(mut self, violations: Vec<RuleViolation>) -> Self {
+        self.violations = violations;
+        self
+    }
+
     pub fn meta(&self) -> &AnalysisMetadata {
         &self.meta
     }
@@ -79,24 +100,223 @@ impl AnalysisReport {
         &self.components
     }
 
+    pub fn with_diagnostics(mut self, diagnostics: Vec<Diagnostic>) -> Self {
+        self.diagnostics = diagnostics;
+        self
+    }
+
+    pub fn violations(&self) -> &[RuleViolation] {
+        &self.violations
+    }
+
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
     pub fn components_mut(&mut self) -> &mut Vec<ComponentUsageAggregate> {
         &mut self.components
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct AnalysisMetadata {
+    /// Version of the report shape, bumped whenever it changes
+    schema_version: u32,
     base_path: String,
+    analyzer_version: String,
+    /// Start of the analysis in RFC 3339 (UTC)
+    timestamp: String,
+    /// Name and version from the root `package.json`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    package: Option<Package>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    git: Option<GitInfo>,
+    /// Configuration the analysis ran with
+    #[serde(skip_serializing_if = "Option::is_none")]
+    config: Option<AnalyzerConfig>,
+    counts: AnalysisCounts,
+    durations: PhaseDurations,
 }
 
 impl AnalysisMetadata {
     pub fn new(base_path: PathBuf) -> Self {
         Self {
+            schema_version: SCHEMA_VERSION,
             base_path: base_path.display().to_string(),
+            analyzer_version: env!("CARGO_PKG_VERSION").to_string(),
+            timestamp: format_rfc3339(SystemTime::now()),
+            package: None,
+            git: None,
+            config: None,
+            counts: AnalysisCounts::default(),
+            durations: PhaseDurations::default(),
         }
     }
 
+    pub fn with_package(mut self, package: Package) -> Self {
+        self.package = Some(package);
+        self
+    }
+
+    pub fn with_git(mut self, git: Option<GitInfo>) -> Self {
+        self.git = git;
+        self
+    }
+
+    pub fn with_config(mut self, config: AnalyzerConfig) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    /// Set the start of the analysis, which defaults to the creation of the metadata
+    pub fn with_started_at(mut self, started_at: SystemTime) -> Self {
+        self.timestamp = format_rfc3339(started_at);
+        self
+    }
+
+    pub fn with_counts(mut self, counts: AnalysisCounts) -> Self {
+        self.counts = counts;
+        self
+    }
+
+    pub fn with_durations(mut self, durations: PhaseDurations) -> Self {
+        self.durations = durations;
+        self
+    }
+
+    pub fn schema_version(&self) -> u32 {
+        self.schema_version
+    }
+
     pub fn base_path(&self) -> &str {
         &self.base_path
     }
+
+    pub fn analyzer_version(&self) -> &str {
+        &self.analyzer_version
+    }
+
+    pub fn timestamp(&self) -> &str {
+        &self.timestamp
+    }
+
+    pub fn package(&self) -> Option<&Package> {
+        self.package.as_ref()
+    }
+
+    pub fn git(&self) -> Option<&GitInfo> {
+        self.git.as_ref()
+    }
+
+    pub fn config(&self) -> Option<&AnalyzerConfig> {
+        self.config.as_ref()
+    }
+
+    pub fn counts(&self) -> &AnalysisCounts {
+        &self.counts
+    }
+
+    pub fn durations(&self) -> &PhaseDurations {
+        &self.durations
+    }
+}
+
+/// Git state of the analyzed project
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct GitInfo {
+    pub commit: String,
+    /// `None` on a detached HEAD
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+}
+
+impl GitInfo {
+    /// Read the current commit and branch with `git`; `None` outside a repository
+    pub fn detect(root: &Path) -> Option<Self> {
+        let git = |args: &[&str]| {
+            let output = Command::new("git")
+                .arg("-C")
+                .arg(root)
+                .args(args)
+                .output()
+                .ok()?;
+            output
+                .status
+                .success()
+                .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        };
+
+        let commit = git(&["rev-parse", "HEAD"])?;
+        let branch = git(&["rev-parse", "--abbrev-ref", "HEAD"]).filter(|b| b != "HEAD");
+        Some(Self { commit, branch })
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, JsonSchema)]
+pub struct AnalysisCounts {
+    pub files_walked: usize,
+    pub files_with_jsx: usize,
+    pub usages: usize,
+    /// JSX elements whose component could not be identified
+    pub unresolved: usize,
+}
+
+/// Time spent in each phase, in milliseconds
+///
+/// `parse` and `resolve` run in parallel and are summed over all files, so they can exceed
+/// `total`.
+#[derive(Debug, Clone, Default, Serialize, JsonSchema)]
+pub struct PhaseDurations {
+    pub walk: f64,
+    pub parse: f64,
+    pub resolve: f64,
+    pub aggregate: f64,
+    pub total: f64,
+}
+
+impl PhaseDurations {
+    pub fn millis(duration: Duration) -> f64 {
+        duration.as_secs_f64() * 1000.0
+    }
+}
+
+/// Format a time as RFC 3339 in UTC with second precision
+fn format_rfc3339(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let (days, rem) = (secs / 86_400, secs % 86_400);
+
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm)
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        rem / 3_600,
+        rem % 3_600 / 60,
+        rem % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_rfc3339() {
+        assert_eq!(format_rfc3339(UNIX_EPOCH), "1970-01-01T00:00:00Z");
+        assert_eq!(
+            format_rfc3339(UNIX_EPOCH + Duration::from_secs(1_709_251_199)),
+            "2024-02-29T23:59:59Z"
+        );
+    }
 }
@@ -0,0 +1,323 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::analyze::{ComponentUsage, ComponentUsageAggregate, SimplifiedProp};
+use crate::check::ComponentSelector;
+use crate::parser::Span;
+use crate::{AnalysisError, Result};
+
+/// Key used for props coming from a spread that could not be resolved
+const UNRESOLVED_SPREAD_KEY: &str = "(spread)";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    #[default]
+    Error,
+    Warning,
+}
+
+/// What a rule asserts about the usages of the selected component
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RuleKind {
+    /// The prop must not be passed, or must not be passed with `value`
+    BannedProp {
+        prop: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        value: Option<String>,
+    },
+    /// The prop must always be passed
+    RequiredProp { prop: String },
+    /// The component must not be used, optionally naming its replacement
+    Deprecated {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        replacement: Option<String>,
+    },
+}
+
+impl RuleKind {
+    fn default_id(&self) -> &'static str {
+        match self {
+            Self::BannedProp { .. } => "banned-prop",
+            Self::RequiredProp { .. } => "required-prop",
+            Self::Deprecated { .. } => "deprecated",
+        }
+    }
+}
+
+/// A declarative rule, e.g. "`Icon` from `@acme/ui` must have `aria-label`"
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Rule {
+    /// Identifier reported with violations; defaults to the rule kind
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub component: ComponentSelector,
+    #[serde(flatten)]
+    pub kind: RuleKind,
+    #[serde(default)]
+    pub severity: Severity,
+    /// Custom message; a message is generated from the rule when omitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl Rule {
+    pub fn id(&self) -> &str {
+        self.id.as_deref().unwrap_or(self.kind.default_id())
+    }
+
+    /// Parse a JSON array of rules
+    pub fn parse_list(json: &str) -> Result<Vec<Self>> {
+        serde_json::from_str(json)
+            .map_err(|e| AnalysisError::new(format!("Failed to parse rules: {e}")))
+    }
+
+    fn check_usage(&self, component_name: &str, usage: &ComponentUsage) -> Option<String> {
+        let props = usage.simplified_props();
+        let violated = match &self.kind {
+            RuleKind::BannedProp { prop, value } => props
+                .iter()
+                .any(|p| prop_matches(p, prop, value.as_deref())),
+            RuleKind::RequiredProp { prop } => {
+                // An unresolved spread may provide the prop, so it cannot be reported
+                !props
+                    .iter()
+                    .any(|p| p.key() == prop || p.key() == UNRESOLVED_SPREAD_KEY)
+            }
+            RuleKind::Deprecated { .. } => true,
+        };
+        if !violated {
+            return None;
+        }
+
+        if let Some(message) = &self.message {
+            return Some(message.clone());
+        }
+        Some(match &self.kind {
+            RuleKind::BannedProp {
+                prop,
+                value: Some(value),
+            } => format!("`{component_name}` must not receive `{prop}=\"{value}\"`"),
+            RuleKind::BannedProp { prop, value: None } => {
+                format!("`{component_name}` must not receive `{prop}`")
+            }
+            RuleKind::RequiredProp { prop } => format!("`{component_name}` must have `{prop}`"),
+            RuleKind::Deprecated {
+                replacement: Some(replacement),
+            } => format!("`{component_name}` is deprecated, use `{replacement}`"),
+            RuleKind::Deprecated { replacement: None } => {
+                format!("`{component_name}` is deprecated")
+            }
+        })
+    }
+}
+
+/// Whether the prop has the key and, if given, the value (matched against value, raw text or
+/// conditional branch values)
+pub(crate) fn prop_matches(prop: &SimplifiedProp, key: &str, value: Option<&str>) -> bool {
+    prop.key() == key
+        && value.is_none_or(|value| {
+            prop.value() == Some(value)
+                || prop.raw() == value
+                || prop.branch_values().iter().any(|v| v == value)
+        })
+}
+
+/// A usage that breaks a rule
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct RuleViolation {
+    pub rule_id: String,
+    pub severity: Severity,
+    pub message: String,
+    pub component_id: String,
+    pub component_name: String,
+    pub file_path: String,
+    pub span: Span,
+}
+
+/// Evaluate the rules against every usage of the matching components
+pub fn evaluate(components: &[ComponentUsageAggregate], rules: &[Rule]) -> Vec<RuleViolation> {
+    let mut violations = Vec::new();
+
+    for rule in rules {
+        for aggregate in components.iter().filter(|a| rule.component.matches(a)) {
+            for usage in aggregate.usages() {
+                if let Some(message) = rule.check_usage(aggregate.display_name(), usage) {
+                    let location = usage.occurrence().location();
+                    violations.push(RuleViolation {
+                        rule_id: rule.id().to_string(),
+                        severity: rule.severity,
+                        message,
+                        component_id: aggregate.id().to_string(),
+                        component_name: aggregate.display_name().to_string(),
+                        file_path: location.file().display_path(),
+                        span: location.span().clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    violations.sort_by(|a, b| {
+        a.file_path
+            .cmp(&b.file_path)
+            .then(a.span.start().cmp(&b.span.start()))
+    });
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AnalysisService;
+    use crate::config::AnalyzerConfig;
+    use crate::testing::TestProject;
+
+    #[test]
+    fn test_parse_rules() {
+        let rules = Rule::parse_list(
+            r#"[
+                { "component": { "name": "Button", "package": "@acme/ui" },
+                  "kind": "banned_prop", "prop": "variant", "value": "danger" },
+                { "component": { "name": "Icon" }, "kind": "required_prop", "prop": "aria-label",
+                  "severity": "warning" },
+                { "id": "no-legacy-modal", "component": { "name": "LegacyModal" },
+                  "kind": "deprecated", "replacement": "Dialog" }
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(rules.len(), 3);
+        assert_eq!(rules[0].id(), "banned-prop");
+        assert_eq!(
+            rules[0].kind,
+            RuleKind::BannedProp {
+                prop: "variant".to_string(),
+                value: Some("danger".to_string())
+            }
+        );
+        assert_eq!(rules[1].severity, Severity::Warning);
+        assert_eq!(rules[2].id(), "no-legacy-modal");
+    }
+
+    #[test]
+    fn test_evaluate_rules() {
+        let project = TestProject::new(&[
+            (".ignore", "node_modules"),
+            (
+                "node_modules/@acme/ui/package.json",
+                r#"{"name":"@acme/ui","version":"1.0.0","main":"index.js"}"#,
+            ),
+            (
+                "node_modules/@acme/ui/index.js",
+                "export const Button = () => null;\nexport const Icon = () => null;\nexport const LegacyModal = () => null;",
+            ),
+            (
+                "src/Button.tsx",
+                "export const Button = (props) => <button {...props} />;",
+            ),
+            (
+                "src/App.tsx",
+                r#"import { Button, Icon, LegacyModal } from "@acme/ui";
+import { Button as LocalButton } from "./Button";
+
+export const App = ({ danger, iconProps }) => (
+  <>
+    <Button variant="danger" />
+    <Button variant={danger ? "danger" : "primary"} />
+    <Button variant="primary" />
+    <LocalButton variant="danger" />
+    <Icon aria-label="Close" />
+    <Icon />
+    <Icon {...iconProps} />
+    <LegacyModal />
+  </>
+);"#,
+            ),
+        ]);
+        let rules = Rule::parse_list(
+            r#"[
+                { "component": { "name": "Button", "package": "@acme/ui" },
+                  "kind": "banned_prop", "prop": "variant", "value": "danger" },
+                { "component": { "name": "Icon" }, "kind": "required_prop", "prop": "aria-label",
+                  "severity": "warning" },
+                { "id": "no-legacy-modal", "component": { "name": "LegacyModal" },
+                  "kind": "deprecated", "replacement": "Dialog" },
+                { "id": "local-button", "component": { "name": "Button", "path": "src/*" },
+                  "kind": "banned_prop", "prop": "variant", "message": "Use the shared Button" }
+            ]"#,
+        )
+        .unwrap();
+        let config = AnalyzerConfig {
+            include_native_elements: false,
+            rules,
+            ..AnalyzerConfig::default()
+        };
+        let report = AnalysisService::new(config).run(project.root()).unwrap();
+
+        let violations: Vec<(&str, Severity, u32, u32, &str)> = report
+            .violations()
+            .iter()
+            .map(|v| {
+                (
+                    v.rule_id.as_str(),
+                    v.severity,
+                    v.span.start_line(),
+                    v.span.start_col(),
+                    v.message.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            violations,
+            vec![
+                (
+                    "banned-prop",
+                    Severity::Error,
+                    6,
+                    5,
+                    "`Button` must not receive `variant=\"danger\"`"
+                ),
+                // A conditional matches through its literal branches
+                (
+                    "banned-prop",
+                    Severity::Error,
+                    7,
+                    5,
+                    "`Button` must not receive `variant=\"danger\"`"
+                ),
+                // Selected by path, the package selector skips this one
+                (
+                    "local-button",
+                    Severity::Error,
+                    9,
+                    5,
+                    "Use the shared Button"
+                ),
+                (
+                    "required-prop",
+                    Severity::Warning,
+                    11,
+                    5,
+                    "`Icon` must have `aria-label`"
+                ),
+                // `<Icon {...iconProps} />` on line 12 is not reported: the unresolved spread
+                // may pass `aria-label`
+                (
+                    "no-legacy-modal",
+                    Severity::Error,
+                    13,
+                    5,
+                    "`LegacyModal` is deprecated, use `Dialog`"
+                ),
+            ]
+        );
+        assert!(
+            report
+                .violations()
+                .iter()
+                .all(|v| v.file_path == "src/App.tsx")
+        );
+    }
+}
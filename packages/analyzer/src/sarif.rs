@@ -0,0 +1,212 @@
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+
+use crate::parser::Span;
+use crate::result::AnalysisReport;
+use crate::rules::Severity;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const INFORMATION_URI: &str = "https://github.com/IkumaTadokoro/cuin";
+
+/// Convert rule violations and diagnostics of the report into a SARIF 2.1.0 log
+///
+/// Artifact URIs are project-relative and resolved against the `%SRCROOT%` base. Parse errors
+/// are only reported for files that may hold elements.
+pub fn to_sarif(report: &AnalysisReport) -> Value {
+    let mut rules: BTreeMap<&str, &str> = BTreeMap::new();
+    let mut results = Vec::new();
+
+    for violation in report.violations() {
+        rules
+            .entry(&violation.rule_id)
+            .or_insert("Component usage rule");
+        results.push(result(
+            &violation.rule_id,
+            violation.severity,
+            &violation.message,
+            &violation.file_path,
+            Some(&violation.span),
+        ));
+    }
+
+    for diagnostic in report.diagnostics() {
+        rules
+            .entry(&diagnostic.code)
+            .or_insert("Analyzer diagnostic");
+        results.push(result(
+            &diagnostic.code,
+            diagnostic.severity,
+            &diagnostic.message,
+            &diagnostic.file_path,
+            diagnostic.span.as_ref(),
+        ));
+    }
+
+    let rules: Vec<Value> = rules
+        .into_iter()
+        .map(|(id, description)| {
+            json!({
+                "id": id,
+                "shortDescription": { "text": description },
+            })
+        })
+        .collect();
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "cuin",
+                    "informationUri": INFORMATION_URI,
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }],
+    })
+}
+
+fn result(
+    rule_id: &str,
+    severity: Severity,
+    message: &str,
+    file_path: &str,
+    span: Option<&Span>,
+) -> Value {
+    let mut physical_location = json!({
+        "artifactLocation": {
+            "uri": file_path.replace('\\', "/"),
+            "uriBaseId": "%SRCROOT%",
+        }
+    });
+    if let Some(span) = span {
+        physical_location["region"] = json!({
+            "startLine": span.start_line(),
+            "startColumn": span.start_col(),
+            "endLine": span.end_line(),
+            "endColumn": span.end_col(),
+        });
+    }
+
+    json!({
+        "ruleId": rule_id,
+        "level": level(severity),
+        "message": { "text": message },
+        "locations": [{ "physicalLocation": physical_location }],
+    })
+}
+
+fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostic::Diagnostic;
+    use crate::parser::SourceFile;
+    use crate::result::AnalysisMetadata;
+    use crate::rules::RuleViolation;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_diagnostic_to_sarif() {
+        let file = SourceFile::new_for_test(
+            PathBuf::from("/test/src/App.tsx"),
+            PathBuf::from("src/App.tsx"),
+        );
+        let span = Span::new(10, 12, 2, 3, 2, 5);
+        let report = AnalysisReport::new(AnalysisMetadata::new(PathBuf::from("/test")), vec![])
+            .with_diagnostics(vec![Diagnostic::parse_error(
+                &file,
+                "Unexpected token",
+                Some(span),
+            )]);
+
+        let sarif = to_sarif(&report);
+        let result = &sarif["runs"][0]["results"][0];
+
+        assert_eq!(sarif["version"], "2.1.0");
+        assert_eq!(result["ruleId"], "parse-error");
+        assert_eq!(result["level"], "warning");
+        let location = &result["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "src/App.tsx");
+        assert_eq!(location["region"]["startLine"], 2);
+        assert_eq!(location["region"]["endColumn"], 5);
+    }
+
+    #[test]
+    fn test_violations_to_sarif() {
+        let violation = |rule_id: &str, severity, file_path: &str, span| RuleViolation {
+            rule_id: rule_id.to_string(),
+            severity,
+            message: format!("{rule_id} violated"),
+            component_id: "1".to_string(),
+            component_name: "Button".to_string(),
+            file_path: file_path.to_string(),
+            span,
+        };
+        let report = AnalysisReport::new(AnalysisMetadata::new(PathBuf::from("/test")), vec![])
+            .with_violations(vec![
+                violation(
+                    "banned-prop",
+                    Severity::Error,
+                    "src/App.tsx",
+                    Span::new(40, 60, 3, 5, 3, 25),
+                ),
+                violation(
+                    "required-prop",
+                    Severity::Warning,
+                    "src\\pages\\Home.tsx",
+                    Span::new(0, 8, 1, 1, 1, 9),
+                ),
+                violation(
+                    "banned-prop",
+                    Severity::Error,
+                    "src/Form.tsx",
+                    Span::new(10, 20, 2, 1, 2, 11),
+                ),
+            ]);
+
+        let sarif = to_sarif(&report);
+        let run = &sarif["runs"][0];
+
+        // One descriptor per rule id
+        let rules: Vec<&str> = run["tool"]["driver"]["rules"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|rule| rule["id"].as_str().unwrap())
+            .collect();
+        assert_eq!(rules, ["banned-prop", "required-prop"]);
+        assert_eq!(
+            run["tool"]["driver"]["rules"][0]["shortDescription"]["text"],
+            "Component usage rule"
+        );
+
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0]["ruleId"], "banned-prop");
+        assert_eq!(results[0]["level"], "error");
+        assert_eq!(results[0]["message"]["text"], "banned-prop violated");
+        assert_eq!(
+            results[0]["locations"][0]["physicalLocation"],
+            json!({
+                "artifactLocation": { "uri": "src/App.tsx", "uriBaseId": "%SRCROOT%" },
+                "region": { "startLine": 3, "startColumn": 5, "endLine": 3, "endColumn": 25 },
+            })
+        );
+        assert_eq!(results[1]["ruleId"], "required-prop");
+        assert_eq!(results[1]["level"], "warning");
+        assert_eq!(
+            results[1]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "src/pages/Home.tsx"
+        );
+    }
+}
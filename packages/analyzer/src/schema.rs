@@ -0,0 +1,202 @@
+use schemars::generate::SchemaSettings;
+use schemars::{JsonSchema, Schema, SchemaGenerator};
+use serde_json::Value;
+use std::borrow::Cow;
+
+use crate::analyze::{
+    ComponentIdentity, ComponentUsageAggregate, RenderContext, SerializableComponentGroup,
+    SerializableComponentUsage, SerializableProp, UsagePackageSchema,
+};
+use crate::parser::Span;
+use crate::result::AnalysisReport;
+
+/// Version of the serialized report shape
+///
+/// Bump this whenever the shape changes, then regenerate `schema/report.schema.json` with
+/// `UPDATE_SCHEMA=1 cargo test`.
+pub const SCHEMA_VERSION: u32 = 9;
+
+/// JSON Schema of the serialized [`AnalysisReport`]
+pub fn report_schema() -> Value {
+    let mut schema = SchemaSettings::draft2020_12()
+        .for_serialize()
+        .into_generator()
+        .into_root_schema_for::<AnalysisReport>()
+        .to_value();
+    schema["x-schema-version"] = SCHEMA_VERSION.into();
+    schema
+}
+
+// The types below mirror hand-written `Serialize` impls so the schema matches their output.
+
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ComponentIdentityShape {
+    Internal {
+        canonical_path: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        name: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        version: Option<String>,
+    },
+    External {
+        name: String,
+        version: String,
+    },
+    Native,
+}
+
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum UsagePackageShape {
+    Internal { name: String, version: String },
+    External { name: String, version: String },
+    Native,
+}
+
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+struct ComponentUsageShape {
+    file_path: String,
+    props: Vec<SerializableProp>,
+    raw: String,
+    span: Span,
+    import_specifier: Option<String>,
+    resolved_path: Option<String>,
+    code_split: bool,
+    context: RenderContext,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    package: Option<UsagePackageSchema>,
+}
+
+macro_rules! schema_as {
+    ($ty:ty, $shape:ty, $name:literal) => {
+        impl JsonSchema for $ty {
+            fn schema_name() -> Cow<'static, str> {
+                $name.into()
+            }
+
+            fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+                <$shape>::json_schema(generator)
+            }
+        }
+    };
+}
+
+schema_as!(
+    ComponentIdentity,
+    ComponentIdentityShape,
+    "ComponentIdentity"
+);
+schema_as!(UsagePackageSchema, UsagePackageShape, "UsagePackageSchema");
+schema_as!(
+    SerializableComponentUsage,
+    ComponentUsageShape,
+    "ComponentUsage"
+);
+schema_as!(
+    ComponentUsageAggregate,
+    SerializableComponentGroup,
+    "ComponentGroup"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TestProject;
+    use std::path::Path;
+
+    /// Fails when the serialized shape differs from the committed schema
+    ///
+    /// A shape change must come with a `SCHEMA_VERSION` bump and a regenerated schema file;
+    /// `UPDATE_SCHEMA=1` refuses to rewrite the file until the version is bumped.
+    #[test]
+    fn test_report_schema_is_up_to_date() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("schema/report.schema.json");
+        let generated = format!(
+            "{}\n",
+            serde_json::to_string_pretty(&report_schema()).unwrap()
+        );
+
+        let committed = std::fs::read_to_string(&path).unwrap_or_default();
+        if committed == generated {
+            return;
+        }
+
+        let committed_version = serde_json::from_str::<Value>(&committed)
+            .ok()
+            .and_then(|schema| schema["x-schema-version"].as_u64());
+        assert_ne!(
+            committed_version,
+            Some(u64::from(SCHEMA_VERSION)),
+            "The report shape changed; bump SCHEMA_VERSION and run `UPDATE_SCHEMA=1 cargo test`"
+        );
+
+        if std::env::var_os("UPDATE_SCHEMA").is_some() {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, &generated).unwrap();
+            return;
+        }
+        panic!("schema/report.schema.json is outdated; run `UPDATE_SCHEMA=1 cargo test`");
+    }
+
+    /// Whether `value` has the keys the schema declares (no more, no fewer)
+    fn conforms(value: &Value, schema: &Value, root: &Value) -> bool {
+        if let Some(reference) = schema["$ref"].as_str() {
+            let name = reference.trim_start_matches("#/$defs/");
+            return conforms(value, &root["$defs"][name], root);
+        }
+        if let Some(variants) = schema["anyOf"].as_array().or(schema["oneOf"].as_array()) {
+            return variants.iter().any(|v| conforms(value, v, root));
+        }
+        if let Some(constant) = schema.get("const") {
+            return value == constant;
+        }
+
+        match value {
+            Value::Object(map) => {
+                let properties = schema["properties"].as_object();
+                let known = map.iter().all(|(k, v)| {
+                    properties
+                        .and_then(|p| p.get(k))
+                        .is_some_and(|s| conforms(v, s, root))
+                });
+                let required = schema["required"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .all(|k| k.as_str().is_some_and(|k| map.contains_key(k)));
+                known && required
+            }
+            Value::Array(items) => items
+                .iter()
+                .all(|item| conforms(item, &schema["items"], root)),
+            _ => true,
+        }
+    }
+
+    #[test]
+    fn test_serialized_report_matches_schema() {
+        let project = TestProject::new(&[
+            (
+                "src/Button.tsx",
+                "export const Button = (props) => <button {...props} />;",
+            ),
+            (
+                "src/App.tsx",
+                r#"import { Button } from "./Button";
+export const App = () => <div><Button variant="primary">OK</Button></div>;"#,
+            ),
+        ]);
+        let report = crate::AnalysisService::default()
+            .run(project.root())
+            .unwrap();
+
+        let value = serde_json::to_value(&report).unwrap();
+        let schema = report_schema();
+        assert!(!report.components().is_empty());
+        assert!(conforms(&value, &schema, &schema));
+    }
+}
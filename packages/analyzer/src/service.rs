@@ -1,15 +1,25 @@
 use rayon::prelude::*;
+use schemars::JsonSchema;
 use serde::Serialize;
+use std::collections::HashSet;
+use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, Instant, SystemTime};
 
 use crate::AnalysisError;
-use crate::analyze::{AnalysisContext, Analyzer, ComponentUsage, group_by_identity};
+use crate::analyze::{
+    AnalysisContext, Analyzer, ComponentUsage, FileAnalysis, group_by_identity, roll_up_wrapped,
+};
 use crate::config::AnalyzerConfig;
+use crate::diagnostic::Diagnostic;
 use crate::resolver::{FileSystemContext, ModuleResolver, load_package_info};
-use crate::result::AnalysisReport;
+use crate::result::{AnalysisCounts, AnalysisMetadata, AnalysisReport, PhaseDurations};
+use crate::rules;
+use crate::stream::{StreamGranularity, StreamWriter};
 use crate::walk;
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, JsonSchema)]
 pub struct Package {
     name: String,
     version: String,
@@ -68,9 +78,116 @@ impl AnalysisService {
     }
 
     pub fn run(&self, input_path: &Path) -> Result<AnalysisReport, AnalysisError> {
+        let started = Instant::now();
+        let started_at = SystemTime::now();
+        let prepared = self.prepare(input_path)?;
+        let walk_duration = started.elapsed();
+
+        let analyzer = Analyzer::new();
+        let results: Vec<FileAnalysis> = prepared
+            .target_files
+            .par_iter()
+            .map(|file_path| analyze_or_diagnose(&analyzer, file_path, &prepared.context))
+            .collect();
+
+        let aggregate_started = Instant::now();
+        let mut stats = RunStats::new(prepared.target_files.len());
+        let mut all_usages: Vec<ComponentUsage> = Vec::new();
+        let mut diagnostics: Vec<Diagnostic> = Vec::new();
+        for result in results {
+            stats.record(&result);
+            all_usages.extend(result.usages);
+            diagnostics.extend(result.diagnostics);
+        }
+
+        let mut aggregates = group_by_identity(all_usages, self.config.id_options);
+        if self.config.roll_up_wrapped_usages {
+            roll_up_wrapped(&mut aggregates);
+        }
+
+        let violations = rules::evaluate(&aggregates, &self.config.rules);
+
+        let metadata = stats.into_metadata(
+            &prepared.context,
+            started_at,
+            walk_duration,
+            aggregate_started.elapsed(),
+            started.elapsed(),
+        );
+        Ok(AnalysisReport::new(metadata, aggregates)
+            .with_violations(violations)
+            .with_diagnostics(diagnostics))
+    }
+
+    /// Analyze files in parallel and write NDJSON records as each file finishes
+    ///
+    /// Usages are never held in memory all at once: at most a few analyzed files wait for the
+    /// writer, and a write error stops the analysis. Wrapper roll-up and rules are not applied;
+    /// use [`crate::stream::aggregate_stream`] to aggregate the written stream.
+    pub fn run_streaming<W: Write>(
+        &self,
+        input_path: &Path,
+        writer: W,
+        granularity: StreamGranularity,
+    ) -> Result<AnalysisMetadata, AnalysisError> {
+        let started = Instant::now();
+        let started_at = SystemTime::now();
+        let prepared = self.prepare(input_path)?;
+        let walk_duration = started.elapsed();
+
+        let analyzer = Analyzer::new();
+        let mut stream = StreamWriter::new(writer, granularity, self.config.id_options);
+        let mut stats = RunStats::new(prepared.target_files.len());
+
+        // Bounded so that analyzed files wait for a slow writer instead of piling up
+        let (sender, receiver) =
+            mpsc::sync_channel::<FileAnalysis>(2 * rayon::current_num_threads());
+        std::thread::scope(|scope| -> Result<(), AnalysisError> {
+            let (analyzer, prepared) = (&analyzer, &prepared);
+            scope.spawn(move || {
+                // Sending fails once the receiver is dropped after a write error, which stops
+                // the remaining files from being analyzed
+                let _ = prepared.target_files.par_iter().try_for_each_with(
+                    sender,
+                    |sender, file_path| {
+                        sender.send(analyze_or_diagnose(analyzer, file_path, &prepared.context))
+                    },
+                );
+            });
+
+            for result in receiver {
+                stats.record(&result);
+                stream.write_file(&result)?;
+            }
+            Ok(())
+        })?;
+
+        let metadata = stats.into_metadata(
+            &prepared.context,
+            started_at,
+            walk_duration,
+            Duration::ZERO,
+            started.elapsed(),
+        );
+        stream.write_summary(&metadata)?;
+        Ok(metadata)
+    }
+
+    fn prepare(&self, input_path: &Path) -> Result<PreparedRun, AnalysisError> {
         let project = self.setup_project(input_path)?;
 
-        let target_files = walk::collect_files(&project.input_path, &self.config.target_extensions);
+        let mut target_files =
+            walk::collect_files(&project.input_path, &self.config.target_extensions);
+        if !self.config.dependency_packages.is_empty() {
+            // node_modules is only skipped by the walk when it is ignored
+            let walked: HashSet<PathBuf> = target_files.iter().cloned().collect();
+            let package_files = walk::collect_package_files(
+                project.project_context.root(),
+                &self.config.dependency_packages,
+                &self.config.target_extensions,
+            );
+            target_files.extend(package_files.into_iter().filter(|f| !walked.contains(f)));
+        }
         if target_files.is_empty() {
             return Err(AnalysisError::new("No target files found"));
         }
@@ -83,20 +200,10 @@ impl AnalysisService {
             self.config.clone(),
         );
 
-        let analyzer = Analyzer::new();
-        let all_usages: Vec<ComponentUsage> = target_files
-            .par_iter()
-            .flat_map(|file_path| {
-                analyzer
-                    .analyze_file(file_path, &context)
-                    .unwrap_or_default()
-            })
-            .collect();
-
-        let aggregates = group_by_identity(all_usages);
-
-        let metadata = context.metadata();
-        Ok(AnalysisReport::new(metadata, aggregates))
+        Ok(PreparedRun {
+            context,
+            target_files,
+        })
     }
 
     fn setup_project(&self, input_path: &Path) -> Result<ProjectSetup, AnalysisError> {
@@ -151,3 +258,247 @@ struct ProjectSetup {
     input_path: PathBuf,
     project_context: ProjectContext,
 }
+
+struct PreparedRun {
+    context: AnalysisContext,
+    target_files: Vec<PathBuf>,
+}
+
+fn analyze_or_diagnose(
+    analyzer: &Analyzer,
+    file_path: &Path,
+    context: &AnalysisContext,
+) -> FileAnalysis {
+    analyzer
+        .analyze_file(file_path, context)
+        .unwrap_or_else(|e| FileAnalysis {
+            diagnostics: vec![Diagnostic::analysis_failed(
+                file_path
+                    .strip_prefix(context.project_context().root())
+                    .unwrap_or(file_path)
+                    .display()
+                    .to_string(),
+                e.message(),
+            )],
+            ..FileAnalysis::default()
+        })
+}
+
+/// Counts and durations accumulated over the analyzed files
+struct RunStats {
+    counts: AnalysisCounts,
+    parse: Duration,
+    resolve: Duration,
+}
+
+impl RunStats {
+    fn new(files_walked: usize) -> Self {
+        Self {
+            counts: AnalysisCounts {
+                files_walked,
+                ..AnalysisCounts::default()
+            },
+            parse: Duration::ZERO,
+            resolve: Duration::ZERO,
+        }
+    }
+
+    fn record(&mut self, result: &FileAnalysis) {
+        if result.jsx_elements > 0 {
+            self.counts.files_with_jsx += 1;
+        }
+        self.counts.usages += result.usages.len();
+        self.counts.unresolved += result.unresolved;
+        self.parse += result.parse_duration;
+        self.resolve += result.resolve_duration;
+    }
+
+    fn into_metadata(
+        self,
+        context: &AnalysisContext,
+        started_at: SystemTime,
+        walk: Duration,
+        aggregate: Duration,
+        total: Duration,
+    ) -> AnalysisMetadata {
+        context
+            .metadata()
+            .with_started_at(started_at)
+            .with_counts(self.counts)
+            .with_durations(PhaseDurations {
+                walk: PhaseDurations::millis(walk),
+                parse: PhaseDurations::millis(self.parse),
+                resolve: PhaseDurations::millis(self.resolve),
+                aggregate: PhaseDurations::millis(aggregate),
+                total: PhaseDurations::millis(total),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyze::UsagePackageSchema;
+    use crate::testing::TestProject;
+
+    #[test]
+    fn test_analyze_dependency_packages() {
+        let project = TestProject::new(&[
+            (".ignore", "node_modules"),
+            (
+                "src/App.tsx",
+                r#"import { Card } from "@acme/ui";
+export const App = () => <Card />;"#,
+            ),
+            (
+                "node_modules/@acme/ui/package.json",
+                r#"{
+  "name": "@acme/ui",
+  "version": "2.0.0",
+  "main": "dist/index.cjs",
+  "exports": {
+    ".": { "types": "./dist/index.d.ts", "import": "./dist/index.mjs", "require": "./dist/index.cjs" },
+    "./package.json": "./package.json"
+  }
+}"#,
+            ),
+            (
+                "node_modules/@acme/ui/dist/index.mjs",
+                r#"export { Card } from "./card.mjs";"#,
+            ),
+            (
+                "node_modules/@acme/ui/dist/card.mjs",
+                r#"import { jsx as _jsx } from "react/jsx-runtime";
+import { Box } from "@acme/primitives";
+export const Card = () => _jsx(Box, { padding: 2 });"#,
+            ),
+            // The other build format, the sources and the stories are not published entries
+            (
+                "node_modules/@acme/ui/dist/index.cjs",
+                r#"const { jsx } = require("react/jsx-runtime");
+const { Box } = require("@acme/primitives");
+exports.Card = () => jsx(Box, { padding: 2 });"#,
+            ),
+            (
+                "node_modules/@acme/ui/src/Card.tsx",
+                r#"import { Box } from "@acme/primitives";
+export const Card = () => <Box padding={2} />;"#,
+            ),
+            (
+                "node_modules/@acme/ui/src/Card.stories.tsx",
+                r#"import { Box } from "@acme/primitives";
+export const Default = () => <Box />;"#,
+            ),
+            (
+                "node_modules/@acme/ui/dist/index.d.ts",
+                "export declare const Card: any;",
+            ),
+            (
+                "node_modules/@acme/primitives/package.json",
+                r#"{"name":"@acme/primitives","version":"1.0.0","main":"index.mjs"}"#,
+            ),
+            (
+                "node_modules/@acme/primitives/index.mjs",
+                r#"import { jsx } from "react/jsx-runtime";
+export const Box = (props) => jsx("div", props);"#,
+            ),
+            (
+                "node_modules/other/index.js",
+                r#"import { Box } from "@acme/primitives";
+export const Other = () => <Box />;"#,
+            ),
+        ]);
+        let names = |report: &AnalysisReport| -> Vec<String> {
+            let mut names: Vec<String> = report
+                .components()
+                .iter()
+                .map(|a| a.display_name().to_string())
+                .collect();
+            names.sort();
+            names
+        };
+
+        let report = AnalysisService::default().run(project.root()).unwrap();
+        assert_eq!(names(&report), vec!["Card"]);
+
+        let config = AnalyzerConfig {
+            dependency_packages: vec!["@acme/ui".to_string()],
+            ..AnalyzerConfig::default()
+        };
+        let report = AnalysisService::new(config).run(project.root()).unwrap();
+        assert_eq!(names(&report), vec!["Box", "Card"]);
+
+        let box_usages = report
+            .components()
+            .iter()
+            .find(|a| a.display_name() == "Box")
+            .unwrap()
+            .usages();
+        assert_eq!(box_usages.len(), 1);
+        let box_usage = box_usages[0].to_serializable();
+        assert_eq!(
+            box_usage.file_path,
+            "node_modules/@acme/ui/dist/card.mjs".to_string()
+        );
+        assert!(matches!(
+            box_usage.usage_package_schema,
+            Some(UsagePackageSchema::External { package }) if package.name() == "@acme/ui"
+        ));
+    }
+
+    /// Accepts `limit` bytes, then fails every write like a closed pipe
+    struct BrokenPipe {
+        limit: usize,
+        written: usize,
+        failed_writes: usize,
+    }
+
+    impl Write for BrokenPipe {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            if self.written + buf.len() > self.limit {
+                self.failed_writes += 1;
+                return Err(std::io::ErrorKind::BrokenPipe.into());
+            }
+            self.written += buf.len();
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_streaming_stops_on_write_error() {
+        let files: Vec<(String, String)> = (0..64)
+            .map(|i| {
+                (
+                    format!("src/Page{i}.tsx"),
+                    format!("export const Page{i} = () => <button tabIndex={{{i}}} />;"),
+                )
+            })
+            .collect();
+        let files: Vec<(&str, &str)> = files
+            .iter()
+            .map(|(path, source)| (path.as_str(), source.as_str()))
+            .collect();
+        let project = TestProject::new(&files);
+
+        // Every file writes a usage record; the pipe breaks during the first few of them
+        let mut writer = BrokenPipe {
+            limit: 1024,
+            written: 0,
+            failed_writes: 0,
+        };
+        let result = AnalysisService::default().run_streaming(
+            project.root(),
+            &mut writer,
+            StreamGranularity::Usage,
+        );
+
+        assert!(result.is_err());
+        // Nothing is written after the first failure, neither further files nor the summary
+        assert_eq!(writer.failed_writes, 1);
+        assert!(writer.written > 0);
+    }
+}
@@ -0,0 +1,534 @@
+//! Astro and Vue single-file component front-ends
+//!
+//! The script block (Astro frontmatter, Vue `<script>`) is JavaScript and parsed as such. The
+//! template is HTML with expressions, so [`parse_template`] only reads its tags, attributes and
+//! interpolations, leaving the expressions to the JavaScript parser.
+
+use std::ops::Range;
+
+use crate::mdx::skip_braces;
+
+/// How attribute values and interpolations are written in a template
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TemplateSyntax {
+    /// `name={expression}`, `{...spread}`, `{shorthand}` and `{expression}` children
+    Astro,
+    /// `:name="expression"`, `@event="handler"`, `v-bind="spread"` and `{{ expression }}`
+    /// children
+    Vue,
+}
+
+/// Script and template ranges of a single-file component
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct SfcBlocks {
+    pub scripts: Vec<Range<usize>>,
+    pub template: Option<Range<usize>>,
+    /// Language of the scripts, such as `ts` or `js`
+    pub lang: String,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Template {
+    /// Elements in document order
+    pub elements: Vec<TemplateElement>,
+    /// Ranges of the interpolated expressions, without their delimiters
+    pub expressions: Vec<Range<usize>>,
+}
+
+#[derive(Debug)]
+pub(crate) struct TemplateElement {
+    pub name: String,
+    /// From the opening tag to the end of the closing tag
+    pub span: Range<usize>,
+    pub attributes: Vec<TemplateAttribute>,
+    pub children: Vec<TemplateChild>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum TemplateAttribute {
+    /// `name`, `name="value"` or `name={expression}`
+    Named {
+        name: String,
+        value: Option<AttributeValue>,
+    },
+    /// `{...props}`, the range covers the spread argument
+    Spread(Range<usize>),
+    /// `{name}`, passing the variable of the same name
+    Shorthand(Range<usize>),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum AttributeValue {
+    /// Quoted or unquoted text, the range excludes the quotes
+    Text(Range<usize>),
+    /// `{expression}` or a template literal, the range excludes the braces
+    Expression(Range<usize>),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum TemplateChild {
+    Text(Range<usize>),
+    /// Index into [`Template::elements`]
+    Element(usize),
+    Expression(Range<usize>),
+}
+
+/// Elements that never have children
+const VOID_ELEMENTS: [&str; 14] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// Elements whose content is not markup
+const RAW_TEXT_ELEMENTS: [&str; 2] = ["script", "style"];
+
+/// Split an Astro component into its frontmatter and template
+pub(crate) fn astro_blocks(source: &str) -> SfcBlocks {
+    let start = source.len() - source.trim_start().len();
+    let lang = "ts".to_string();
+    if !source[start..].starts_with("---") {
+        return SfcBlocks {
+            scripts: Vec::new(),
+            template: Some(0..source.len()),
+            lang,
+        };
+    }
+
+    let script_start = (line_end(source, start) + 1).min(source.len());
+    let mut pos = script_start;
+    while pos < source.len() {
+        let end = line_end(source, pos);
+        if source[pos..end].trim_end() == "---" {
+            let frontmatter = script_start..pos;
+            return SfcBlocks {
+                scripts: vec![frontmatter],
+                template: Some((end + 1).min(source.len())..source.len()),
+                lang,
+            };
+        }
+        pos = end + 1;
+    }
+
+    // The frontmatter is never closed
+    let frontmatter = script_start..source.len();
+    SfcBlocks {
+        scripts: vec![frontmatter],
+        template: None,
+        lang,
+    }
+}
+
+/// Find the `<script>` blocks and the `<template>` block of a Vue component
+pub(crate) fn vue_blocks(source: &str) -> SfcBlocks {
+    let mut blocks = SfcBlocks {
+        lang: "js".to_string(),
+        ..SfcBlocks::default()
+    };
+
+    let mut pos = 0;
+    while let Some(i) = source[pos..].find('<') {
+        let start = pos + i;
+        if source[start..].starts_with("<!--") {
+            pos = find_after(source, start, "-->");
+            continue;
+        }
+        if !source.as_bytes()[start + 1..]
+            .first()
+            .is_some_and(u8::is_ascii_alphabetic)
+        {
+            pos = start + 1;
+            continue;
+        }
+        let Some(tag) = read_tag(source, start, TemplateSyntax::Vue) else {
+            break;
+        };
+        if tag.self_closing {
+            pos = tag.end;
+            continue;
+        }
+
+        let close = format!("</{}", tag.name);
+        let content_end = if tag.name == "template" {
+            nested_template_end(source, tag.end)
+        } else {
+            source[tag.end..]
+                .find(&close)
+                .map_or(source.len(), |i| tag.end + i)
+        };
+        match tag.name.as_str() {
+            "script" => {
+                if let Some(lang) = tag.attributes.iter().find_map(|attr| match attr {
+                    TemplateAttribute::Named {
+                        name,
+                        value: Some(AttributeValue::Text(range)),
+                    } if name == "lang" => Some(&source[range.clone()]),
+                    _ => None,
+                }) {
+                    blocks.lang = lang.to_string();
+                }
+                blocks.scripts.push(tag.end..content_end);
+            }
+            "template" if blocks.template.is_none() => {
+                blocks.template = Some(tag.end..content_end);
+            }
+            _ => {}
+        }
+        pos = find_after(source, content_end, ">");
+    }
+
+    blocks
+}
+
+/// Start of the `</template>` closing the template whose content starts at `pos`
+fn nested_template_end(source: &str, pos: usize) -> usize {
+    let mut depth = 1;
+    let mut i = pos;
+    while let Some(offset) = source[i..].find("template") {
+        let at = i + offset;
+        i = at + "template".len();
+        let before = &source[..at];
+        let boundary = source[i..].starts_with(|c: char| c.is_whitespace() || c == '>' || c == '/');
+        if before.ends_with("</") && boundary {
+            depth -= 1;
+            if depth == 0 {
+                return at - 2;
+            }
+        } else if before.ends_with('<') && boundary {
+            let self_closing = source[i..]
+                .find('>')
+                .is_some_and(|end| source[..i + end].ends_with('/'));
+            if !self_closing {
+                depth += 1;
+            }
+        }
+    }
+    source.len()
+}
+
+/// Read the elements, attributes and interpolations of the template at `range`
+///
+/// Unclosed elements end where their parent is closed, as in HTML.
+pub(crate) fn parse_template(
+    source: &str,
+    range: Range<usize>,
+    syntax: TemplateSyntax,
+) -> Template {
+    let bytes = source.as_bytes();
+    let mut template = Template::default();
+    let mut stack: Vec<usize> = Vec::new();
+    let mut pos = range.start;
+    let mut text_start = pos;
+
+    let add_child = |template: &mut Template, stack: &[usize], child: TemplateChild| {
+        if let Some(&parent) = stack.last() {
+            template.elements[parent].children.push(child);
+        }
+    };
+    let flush_text = |template: &mut Template, stack: &[usize], text: Range<usize>| {
+        if !source[text.clone()].trim().is_empty() {
+            add_child(template, stack, TemplateChild::Text(text));
+        }
+    };
+
+    while pos < range.end {
+        let next = bytes.get(pos + 1).copied();
+        match bytes[pos] {
+            b'<' if source[pos..].starts_with("<!") => {
+                flush_text(&mut template, &stack, text_start..pos);
+                let terminator = if source[pos..].starts_with("<!--") {
+                    "-->"
+                } else {
+                    ">"
+                };
+                pos = find_after(source, pos, terminator).min(range.end);
+                text_start = pos;
+            }
+            b'<' if next == Some(b'/') => {
+                flush_text(&mut template, &stack, text_start..pos);
+                let end = find_after(source, pos, ">").min(range.end);
+                let name = source[pos + 2..end].trim_end_matches('>').trim();
+                if let Some(depth) = stack
+                    .iter()
+                    .rposition(|&index| template.elements[index].name == name)
+                {
+                    for &index in &stack[depth + 1..] {
+                        template.elements[index].span.end = pos;
+                    }
+                    template.elements[stack[depth]].span.end = end;
+                    stack.truncate(depth);
+                }
+                pos = end;
+                text_start = pos;
+            }
+            b'<' if next.is_some_and(|c| c.is_ascii_alphabetic()) => {
+                flush_text(&mut template, &stack, text_start..pos);
+                let Some(tag) = read_tag(source, pos, syntax).filter(|tag| tag.end <= range.end)
+                else {
+                    break;
+                };
+
+                let index = template.elements.len();
+                add_child(&mut template, &stack, TemplateChild::Element(index));
+                let name = tag.name.to_ascii_lowercase();
+                let mut element = TemplateElement {
+                    name: tag.name,
+                    span: pos..tag.end,
+                    attributes: tag.attributes,
+                    children: Vec::new(),
+                };
+                pos = tag.end;
+
+                if tag.self_closing || VOID_ELEMENTS.contains(&name.as_str()) {
+                    template.elements.push(element);
+                } else if RAW_TEXT_ELEMENTS.contains(&name.as_str()) {
+                    let close = format!("</{}", element.name);
+                    let close_start = source[pos..range.end]
+                        .find(&close)
+                        .map_or(range.end, |i| pos + i);
+                    pos = find_after(source, close_start, ">").min(range.end);
+                    element.span.end = pos;
+                    template.elements.push(element);
+                } else {
+                    template.elements.push(element);
+                    stack.push(index);
+                }
+                text_start = pos;
+            }
+            b'{' if syntax == TemplateSyntax::Astro => {
+                flush_text(&mut template, &stack, text_start..pos);
+                let end = skip_braces(source, pos).map_or(range.end, |end| end.min(range.end));
+                let expression = pos + 1..(end - 1).max(pos + 1);
+                add_child(
+                    &mut template,
+                    &stack,
+                    TemplateChild::Expression(expression.clone()),
+                );
+                template.expressions.push(expression);
+                pos = end;
+                text_start = pos;
+            }
+            b'{' if syntax == TemplateSyntax::Vue && next == Some(b'{') => {
+                flush_text(&mut template, &stack, text_start..pos);
+                let close = source[pos + 2..range.end]
+                    .find("}}")
+                    .map_or(range.end, |i| pos + 2 + i);
+                let expression = pos + 2..close;
+                add_child(
+                    &mut template,
+                    &stack,
+                    TemplateChild::Expression(expression.clone()),
+                );
+                template.expressions.push(expression);
+                pos = (close + 2).min(range.end);
+                text_start = pos;
+            }
+            _ => pos += 1,
+        }
+    }
+
+    flush_text(&mut template, &stack, text_start..range.end.min(pos));
+    for index in stack {
+        template.elements[index].span.end = range.end;
+    }
+    template
+}
+
+/// Opening tag
+struct Tag {
+    name: String,
+    attributes: Vec<TemplateAttribute>,
+    /// Offset after `>`
+    end: usize,
+    self_closing: bool,
+}
+
+/// Read the opening tag starting with `<` at `start`
+fn read_tag(source: &str, start: usize, syntax: TemplateSyntax) -> Option<Tag> {
+    let bytes = source.as_bytes();
+    let name_end = source[start + 1..]
+        .find(|c: char| c.is_whitespace() || c == '/' || c == '>')
+        .map_or(source.len(), |i| start + 1 + i);
+    let name = source[start + 1..name_end].to_string();
+
+    let mut attributes = Vec::new();
+    let mut i = name_end;
+    loop {
+        while bytes.get(i).is_some_and(u8::is_ascii_whitespace) {
+            i += 1;
+        }
+        match *bytes.get(i)? {
+            b'>' => {
+                return Some(Tag {
+                    name,
+                    attributes,
+                    end: i + 1,
+                    self_closing: false,
+                });
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'>') => {
+                return Some(Tag {
+                    name,
+                    attributes,
+                    end: i + 2,
+                    self_closing: true,
+                });
+            }
+            b'/' => i += 1,
+            b'{' if syntax == TemplateSyntax::Astro => {
+                let end = skip_braces(source, i)?;
+                let inner = i + 1..end - 1;
+                let text = source[inner.clone()].trim();
+                let text_start = inner.start
+                    + (source[inner.clone()].len() - source[inner.clone()].trim_start().len());
+                attributes.push(match text.strip_prefix("...") {
+                    Some(argument) => {
+                        TemplateAttribute::Spread(text_start + 3..text_start + 3 + argument.len())
+                    }
+                    None => TemplateAttribute::Shorthand(text_start..text_start + text.len()),
+                });
+                i = end;
+            }
+            _ => {
+                let attr_end = source[i..]
+                    .find(|c: char| c.is_whitespace() || c == '=' || c == '>' || c == '/')
+                    .map_or(source.len(), |offset| i + offset);
+                let name = source[i..attr_end].to_string();
+                i = attr_end;
+
+                let mut after = i;
+                while bytes.get(after).is_some_and(u8::is_ascii_whitespace) {
+                    after += 1;
+                }
+                let value = if bytes.get(after) == Some(&b'=') {
+                    i = after + 1;
+                    while bytes.get(i).is_some_and(u8::is_ascii_whitespace) {
+                        i += 1;
+                    }
+                    let (value, end) = read_attribute_value(source, i, syntax)?;
+                    i = end;
+                    Some(value)
+                } else {
+                    None
+                };
+                attributes.push(TemplateAttribute::Named { name, value });
+            }
+        }
+    }
+}
+
+/// Read an attribute value starting at `pos`, returning it with the offset after it
+fn read_attribute_value(
+    source: &str,
+    pos: usize,
+    syntax: TemplateSyntax,
+) -> Option<(AttributeValue, usize)> {
+    let bytes = source.as_bytes();
+    match *bytes.get(pos)? {
+        // HTML attribute values may span lines and have no escapes
+        quote @ (b'"' | b'\'') => {
+            let close = pos + 1 + source[pos + 1..].find(quote as char)?;
+            Some((AttributeValue::Text(pos + 1..close), close + 1))
+        }
+        b'{' if syntax == TemplateSyntax::Astro => {
+            let end = skip_braces(source, pos)?;
+            Some((AttributeValue::Expression(pos + 1..end - 1), end))
+        }
+        b'`' if syntax == TemplateSyntax::Astro => {
+            let end = crate::mdx::skip_string(source, pos, b'`');
+            Some((AttributeValue::Expression(pos..end), end))
+        }
+        _ => {
+            let end = source[pos..]
+                .find(|c: char| c.is_whitespace() || c == '>')
+                .map_or(source.len(), |i| pos + i);
+            Some((AttributeValue::Text(pos..end), end))
+        }
+    }
+}
+
+fn line_end(source: &str, pos: usize) -> usize {
+    source[pos..].find('\n').map_or(source.len(), |i| pos + i)
+}
+
+/// Offset after the first `pattern` at or after `pos`, or the end of `source`
+fn find_after(source: &str, pos: usize, pattern: &str) -> usize {
+    source[pos..]
+        .find(pattern)
+        .map_or(source.len(), |i| pos + i + pattern.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_astro_blocks() {
+        let source = "---\nimport { Button } from \"ui\";\n---\n<Button />\n";
+        let blocks = astro_blocks(source);
+        assert_eq!(
+            &source[blocks.scripts[0].clone()],
+            "import { Button } from \"ui\";\n"
+        );
+        assert_eq!(&source[blocks.template.unwrap()], "<Button />\n");
+    }
+
+    #[test]
+    fn test_vue_blocks() {
+        let source = r#"<!-- <template>not this</template> -->
+<template>
+  <div><template v-if="ok"><Button /></template></div>
+</template>
+
+<script setup lang="ts">
+import { Button } from "ui";
+</script>
+
+<style scoped>
+div > p { color: red; }
+</style>
+"#;
+        let blocks = vue_blocks(source);
+        assert_eq!(blocks.lang, "ts");
+        assert_eq!(
+            source[blocks.scripts[0].clone()].trim(),
+            r#"import { Button } from "ui";"#
+        );
+        assert_eq!(
+            source[blocks.template.unwrap()].trim(),
+            r#"<div><template v-if="ok"><Button /></template></div>"#
+        );
+    }
+
+    #[test]
+    fn test_parse_template() {
+        let source = r#"<Card title="Hi" client:load {...rest} {open} count={items.length}>
+  <img src="a.png"><br>
+  Hello {name}!
+  <Button :size="size" @click="go">Go</Button>
+  <!-- <Hidden /> -->
+  <p>Unclosed
+</Card>"#;
+        let template = parse_template(source, 0..source.len(), TemplateSyntax::Astro);
+        let names: Vec<&str> = template.elements.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["Card", "img", "br", "Button", "p"]);
+
+        let card = &template.elements[0];
+        assert_eq!(card.span, 0..source.len());
+        assert_eq!(card.attributes.len(), 5);
+        assert!(matches!(
+            &card.attributes[2],
+            TemplateAttribute::Spread(range) if &source[range.clone()] == "rest"
+        ));
+        assert!(matches!(
+            &card.attributes[4],
+            TemplateAttribute::Named { value: Some(AttributeValue::Expression(range)), .. }
+                if &source[range.clone()] == "items.length"
+        ));
+        assert_eq!(template.expressions.len(), 1);
+        assert_eq!(card.children.len(), 7);
+
+        // The unclosed paragraph ends where its parent is closed
+        let p = &template.elements[4];
+        assert!(source[p.span.clone()].ends_with("Unclosed\n"));
+    }
+}
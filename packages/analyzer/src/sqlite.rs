@@ -0,0 +1,276 @@
+use rusqlite::{Connection, OptionalExtension, Transaction, params};
+use std::path::Path;
+
+use crate::analyze::{ComponentSource, UsagePackageSchema};
+use crate::result::AnalysisReport;
+use crate::service::Package;
+use crate::{AnalysisError, Result};
+
+const SCHEMA: &str = "
+DROP TABLE IF EXISTS props;
+DROP TABLE IF EXISTS usages;
+DROP TABLE IF EXISTS components;
+DROP TABLE IF EXISTS packages;
+DROP TABLE IF EXISTS meta;
+
+CREATE TABLE meta (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL
+);
+
+CREATE TABLE packages (
+    id INTEGER PRIMARY KEY,
+    name TEXT NOT NULL,
+    version TEXT NOT NULL,
+    UNIQUE (name, version)
+);
+
+CREATE TABLE components (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    source_type TEXT NOT NULL,
+    export_name TEXT NOT NULL,
+    canonical_path TEXT,
+    package_id INTEGER REFERENCES packages (id),
+    total_count INTEGER NOT NULL
+);
+
+CREATE TABLE usages (
+    id INTEGER PRIMARY KEY,
+    component_id TEXT NOT NULL REFERENCES components (id),
+    file_path TEXT NOT NULL,
+    start_offset INTEGER NOT NULL,
+    end_offset INTEGER NOT NULL,
+    start_line INTEGER NOT NULL,
+    start_col INTEGER NOT NULL,
+    end_line INTEGER NOT NULL,
+    end_col INTEGER NOT NULL,
+    import_specifier TEXT,
+    code_split INTEGER NOT NULL,
+    in_loop INTEGER NOT NULL,
+    conditional INTEGER NOT NULL,
+    in_render_prop INTEGER NOT NULL,
+    in_prop INTEGER NOT NULL,
+    enclosing_component TEXT,
+    usage_package_id INTEGER REFERENCES packages (id),
+    raw TEXT NOT NULL
+);
+
+CREATE TABLE props (
+    id INTEGER PRIMARY KEY,
+    usage_id INTEGER NOT NULL REFERENCES usages (id),
+    key TEXT NOT NULL,
+    pattern TEXT NOT NULL,
+    value TEXT,
+    raw TEXT NOT NULL,
+    shape TEXT,
+    type_annotation TEXT
+);
+
+CREATE INDEX usages_component_id ON usages (component_id);
+CREATE INDEX usages_file_path ON usages (file_path);
+CREATE INDEX props_usage_id ON props (usage_id);
+CREATE INDEX props_key_value ON props (key, value);
+";
+
+/// Write the report into a SQLite database with normalized tables
+///
+/// Existing tables written by a previous export are replaced.
+pub fn write_sqlite(report: &AnalysisReport, path: &Path) -> Result<()> {
+    let mut connection = Connection::open(path).map_err(to_error)?;
+    let transaction = connection.transaction().map_err(to_error)?;
+    transaction.execute_batch(SCHEMA).map_err(to_error)?;
+
+    write_meta(&transaction, report)?;
+    write_components(&transaction, report)?;
+
+    transaction.commit().map_err(to_error)
+}
+
+fn to_error(error: rusqlite::Error) -> AnalysisError {
+    AnalysisError::new(format!("SQLite export failed: {error}"))
+}
+
+fn write_meta(tx: &Transaction, report: &AnalysisReport) -> Result<()> {
+    let meta = report.meta();
+    let entries = [
+        ("schema_version", meta.schema_version().to_string()),
+        ("base_path", meta.base_path().to_string()),
+        ("analyzer_version", meta.analyzer_version().to_string()),
+        ("timestamp", meta.timestamp().to_string()),
+    ];
+    for (key, value) in entries {
+        tx.execute(
+            "INSERT INTO meta (key, value) VALUES (?1, ?2)",
+            params![key, value],
+        )
+        .map_err(to_error)?;
+    }
+    Ok(())
+}
+
+fn insert_package(tx: &Transaction, package: &Package) -> Result<i64> {
+    let existing = tx
+        .query_row(
+            "SELECT id FROM packages WHERE name = ?1 AND version = ?2",
+            params![package.name(), package.version()],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(to_error)?;
+    if let Some(id) = existing {
+        return Ok(id);
+    }
+
+    tx.execute(
+        "INSERT INTO packages (name, version) VALUES (?1, ?2)",
+        params![package.name(), package.version()],
+    )
+    .map_err(to_error)?;
+    Ok(tx.last_insert_rowid())
+}
+
+fn write_components(tx: &Transaction, report: &AnalysisReport) -> Result<()> {
+    for aggregate in report.components() {
+        let identity = aggregate.identity();
+        let (source_type, canonical_path, package) = match identity.source() {
+            ComponentSource::Internal { canonical_path } => (
+                "internal",
+                Some(canonical_path.as_str()),
+                identity.package(),
+            ),
+            ComponentSource::External { package } => ("external", None, Some(package)),
+            ComponentSource::Native => ("native", None, None),
+        };
+        let package_id = package.map(|p| insert_package(tx, p)).transpose()?;
+
+        tx.execute(
+            "INSERT INTO components
+                (id, name, source_type, export_name, canonical_path, package_id, total_count)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                aggregate.id(),
+                aggregate.display_name(),
+                source_type,
+                identity.export_name().display_name(),
+                canonical_path,
+                package_id,
+                aggregate.statistics().total_count() as i64,
+            ],
+        )
+        .map_err(to_error)?;
+
+        for usage in aggregate.usages() {
+            let usage = usage.to_serializable();
+            let usage_package_id = match &usage.usage_package_schema {
+                Some(UsagePackageSchema::Internal { package })
+                | Some(UsagePackageSchema::External { package }) => {
+                    Some(insert_package(tx, package)?)
+                }
+                _ => None,
+            };
+            let span = &usage.span;
+
+            tx.execute(
+                "INSERT INTO usages
+                    (component_id, file_path, start_offset, end_offset, start_line, start_col,
+                     end_line, end_col, import_specifier, code_split, in_loop, conditional,
+                     in_render_prop, in_prop, enclosing_component, usage_package_id, raw)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16,
+                         ?17)",
+                params![
+                    aggregate.id(),
+                    usage.file_path,
+                    span.start(),
+                    span.end(),
+                    span.start_line(),
+                    span.start_col(),
+                    span.end_line(),
+                    span.end_col(),
+                    usage.import_specifier,
+                    usage.code_split,
+                    usage.context.in_loop,
+                    usage.context.conditional,
+                    usage.context.in_render_prop,
+                    usage.context.in_prop,
+                    usage.context.component,
+                    usage_package_id,
+                    usage.raw,
+                ],
+            )
+            .map_err(to_error)?;
+            let usage_id = tx.last_insert_rowid();
+
+            for prop in &usage.props {
+                tx.execute(
+                    "INSERT INTO props
+                        (usage_id, key, pattern, value, raw, shape, type_annotation)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                    params![
+                        usage_id,
+                        prop.key,
+                        prop.prop_type,
+                        prop.value,
+                        prop.raw,
+                        prop.shape,
+                        prop.type_annotation
+                    ],
+                )
+                .map_err(to_error)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TestProject;
+
+    #[test]
+    fn test_write_sqlite() {
+        let project = TestProject::new(&[
+            (
+                "src/Button.tsx",
+                "export const Button = (props) => <button {...props} />;",
+            ),
+            (
+                "src/App.tsx",
+                r#"import { Button } from "./Button";
+export const App = () => <Button disabled loading />;"#,
+            ),
+            (
+                "src/Page.tsx",
+                r#"import { Button } from "./Button";
+export const Page = () => <Button disabled />;"#,
+            ),
+        ]);
+        let report = crate::AnalysisService::default()
+            .run(project.root())
+            .unwrap();
+        let path = project.root().join("report.db");
+
+        write_sqlite(&report, &path).unwrap();
+        // Writing again replaces the previous export
+        write_sqlite(&report, &path).unwrap();
+
+        let connection = Connection::open(&path).unwrap();
+        let mut statement = connection
+            .prepare(
+                "SELECT DISTINCT u.file_path FROM usages u
+                 JOIN components c ON c.id = u.component_id
+                 WHERE c.name = 'Button'
+                   AND EXISTS (SELECT 1 FROM props p WHERE p.usage_id = u.id AND p.key = 'disabled')
+                   AND EXISTS (SELECT 1 FROM props p WHERE p.usage_id = u.id AND p.key = 'loading')",
+            )
+            .unwrap();
+        let files: Vec<String> = statement
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<std::result::Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(files, vec!["src/App.tsx".to_string()]);
+    }
+}
@@ -0,0 +1,323 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::{BufRead, Write};
+
+use crate::analyze::{
+    ComponentIdentity, ComponentUsage, FileAnalysis, IdOptions, SerializableComponentUsage,
+    SerializableProp, SerializablePropDistribution, SerializablePropUsage, compute_display_name,
+};
+use crate::diagnostic::Diagnostic;
+use crate::result::AnalysisMetadata;
+use crate::{AnalysisError, Result};
+
+/// What each NDJSON line holds
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StreamGranularity {
+    /// One `usage` line per usage and one `diagnostic` line per diagnostic
+    #[default]
+    Usage,
+    /// One `file` line per analyzed file with its usages and diagnostics
+    File,
+}
+
+#[derive(Serialize)]
+struct UsageRecord<'a> {
+    component_id: String,
+    component_name: String,
+    component: &'a ComponentIdentity,
+    #[serde(flatten)]
+    instance: SerializableComponentUsage,
+    /// Object literal properties as `style.margin`, counted in the prop statistics only
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    nested_props: Vec<SerializableProp>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum Record<'a> {
+    Usage(Box<UsageRecord<'a>>),
+    Diagnostic(&'a Diagnostic),
+    File {
+        file_path: String,
+        usages: Vec<UsageRecord<'a>>,
+        diagnostics: &'a [Diagnostic],
+    },
+    /// Last line of the stream
+    Summary {
+        meta: &'a AnalysisMetadata,
+    },
+}
+
+/// Writes analysis results as newline-delimited JSON
+pub struct StreamWriter<W: Write> {
+    writer: W,
+    granularity: StreamGranularity,
+    id_options: IdOptions,
+}
+
+impl<W: Write> StreamWriter<W> {
+    pub fn new(writer: W, granularity: StreamGranularity, id_options: IdOptions) -> Self {
+        Self {
+            writer,
+            granularity,
+            id_options,
+        }
+    }
+
+    pub fn write_file(&mut self, result: &FileAnalysis) -> Result<()> {
+        match self.granularity {
+            StreamGranularity::Usage => {
+                for usage in &result.usages {
+                    let record = Record::Usage(Box::new(self.usage_record(usage)));
+                    self.write_record(&record)?;
+                }
+                for diagnostic in &result.diagnostics {
+                    self.write_record(&Record::Diagnostic(diagnostic))?;
+                }
+            }
+            StreamGranularity::File => {
+                let file_path = result
+                    .usages
+                    .first()
+                    .map(|u| u.occurrence().location().file().display_path())
+                    .or_else(|| result.diagnostics.first().map(|d| d.file_path.clone()));
+                // Files without usages or diagnostics carry no information
+                let Some(file_path) = file_path else {
+                    return Ok(());
+                };
+
+                let record = Record::File {
+                    file_path,
+                    usages: result.usages.iter().map(|u| self.usage_record(u)).collect(),
+                    diagnostics: &result.diagnostics,
+                };
+                self.write_record(&record)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn write_summary(&mut self, meta: &AnalysisMetadata) -> Result<()> {
+        self.write_record(&Record::Summary { meta })?;
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    fn usage_record<'a>(&self, usage: &'a ComponentUsage) -> UsageRecord<'a> {
+        let identity = usage.definition().identity();
+        UsageRecord {
+            component_id: identity.generate_id_with(self.id_options),
+            component_name: compute_display_name(identity, std::slice::from_ref(usage)),
+            component: identity,
+            instance: usage.to_serializable(),
+            nested_props: usage
+                .nested_props()
+                .iter()
+                .map(|p| p.to_serializable())
+                .collect(),
+        }
+    }
+
+    fn write_record(&mut self, record: &Record) -> Result<()> {
+        serde_json::to_writer(&mut self.writer, record)
+            .map_err(|e| AnalysisError::new(format!("Failed to write stream: {e}")))?;
+        self.writer.write_all(b"\n")?;
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum RecordIn {
+    Usage(UsageIn),
+    File {
+        usages: Vec<UsageIn>,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Deserialize)]
+struct UsageIn {
+    component_id: String,
+    component_name: String,
+    component: Value,
+    file_path: String,
+    #[serde(default)]
+    props: Vec<PropIn>,
+    #[serde(default)]
+    nested_props: Vec<PropIn>,
+}
+
+#[derive(Deserialize)]
+struct PropIn {
+    key: String,
+    #[serde(default)]
+    value: Option<String>,
+    raw: String,
+    prop_type: String,
+    #[serde(default)]
+    shape: Option<String>,
+    #[serde(default)]
+    element_count: Option<u32>,
+    #[serde(default)]
+    branch_values: Vec<String>,
+}
+
+/// Per-component statistics computed from a stream
+#[derive(Debug, Clone, Serialize)]
+pub struct StreamComponent {
+    pub id: String,
+    pub name: String,
+    pub package: Value,
+    pub total_count: usize,
+    /// Number of distinct files the component is used in
+    pub files: usize,
+    pub props_usages: Vec<SerializablePropUsage>,
+}
+
+/// (prop_type, value, raw)
+type ValueKey = (String, Option<String>, String);
+/// (shape, element_count, count)
+type ValueCount = (Option<String>, Option<u32>, u32);
+
+#[derive(Default)]
+struct ComponentAccumulator {
+    name: String,
+    package: Value,
+    total_count: usize,
+    files: HashSet<String>,
+    /// Counts per prop and value
+    props: BTreeMap<String, HashMap<ValueKey, ValueCount>>,
+}
+
+/// Aggregate an NDJSON stream written by [`StreamWriter`], one line at a time
+pub fn aggregate_stream<R: BufRead>(reader: R) -> Result<Vec<StreamComponent>> {
+    let mut components: BTreeMap<String, ComponentAccumulator> = BTreeMap::new();
+
+    let mut add = |usage: UsageIn| {
+        let component = components.entry(usage.component_id).or_default();
+        if component.total_count == 0 {
+            component.name = usage.component_name;
+            component.package = usage.component;
+        }
+        component.total_count += 1;
+        component.files.insert(usage.file_path);
+        for prop in usage.props.into_iter().chain(usage.nested_props) {
+            // Each literal branch of a conditional counts as a value of its own
+            let values = if prop.branch_values.is_empty() {
+                vec![prop.value]
+            } else {
+                prop.branch_values.into_iter().map(Some).collect()
+            };
+            let distribution = component.props.entry(prop.key).or_default();
+            for value in values {
+                distribution
+                    .entry((prop.prop_type.clone(), value, prop.raw.clone()))
+                    .or_insert((prop.shape.clone(), prop.element_count, 0))
+                    .2 += 1;
+            }
+        }
+    };
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record: RecordIn = serde_json::from_str(&line).map_err(|e| {
+            AnalysisError::new(format!("Invalid stream record on line {}: {e}", index + 1))
+        })?;
+        match record {
+            RecordIn::Usage(usage) => add(usage),
+            RecordIn::File { usages } => usages.into_iter().for_each(&mut add),
+            RecordIn::Other => {}
+        }
+    }
+
+    let mut result: Vec<StreamComponent> = components
+        .into_iter()
+        .map(|(id, component)| StreamComponent {
+            id,
+            name: component.name,
+            package: component.package,
+            total_count: component.total_count,
+            files: component.files.len(),
+            props_usages: component
+                .props
+                .into_iter()
+                .map(|(key, values)| {
+                    let mut distribution: Vec<SerializablePropDistribution> = values
+                        .into_iter()
+                        .map(|((prop_type, value, raw), (shape, element_count, count))| {
+                            SerializablePropDistribution {
+                                value,
+                                raw,
+                                prop_type,
+                                shape,
+                                element_count,
+                                count,
+                            }
+                        })
+                        .collect();
+                    distribution.sort_by(|a, b| a.raw.cmp(&b.raw).then(a.value.cmp(&b.value)));
+                    SerializablePropUsage { key, distribution }
+                })
+                .collect(),
+        })
+        .collect();
+    result.sort_by(|a, b| b.total_count.cmp(&a.total_count).then(a.name.cmp(&b.name)));
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aggregate_stream() {
+        let stream = [
+            r#"{"kind":"usage","component_id":"1","component_name":"Button","component":{"type":"native"},"file_path":"a.tsx","props":[{"key":"variant","value":"primary","raw":"primary","prop_type":"string"}]}"#,
+            r#"{"kind":"diagnostic","severity":"warning","code":"parse-error","message":"x","file_path":"b.tsx"}"#,
+            r#"{"kind":"file","file_path":"b.tsx","usages":[{"component_id":"1","component_name":"Button","component":{"type":"native"},"file_path":"b.tsx","props":[{"key":"variant","value":"primary","raw":"primary","prop_type":"string"}]}],"diagnostics":[]}"#,
+            r#"{"kind":"summary","meta":{}}"#,
+        ]
+        .join("\n");
+
+        let components = aggregate_stream(stream.as_bytes()).unwrap();
+
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0].total_count, 2);
+        assert_eq!(components[0].files, 2);
+        assert_eq!(components[0].props_usages[0].distribution[0].count, 2);
+    }
+
+    #[test]
+    fn test_aggregate_stream_counts_nested_props() {
+        let project = crate::testing::TestProject::new(&[(
+            "src/App.tsx",
+            r#"export const App = () => <div style={{ margin: 0 }} data-items={[1, 2]} />;"#,
+        )]);
+        let mut stream = Vec::new();
+        crate::AnalysisService::default()
+            .run_streaming(project.root(), &mut stream, StreamGranularity::Usage)
+            .unwrap();
+
+        let components = aggregate_stream(stream.as_slice()).unwrap();
+
+        let props: Vec<(&str, Option<u32>)> = components[0]
+            .props_usages
+            .iter()
+            .map(|p| (p.key.as_str(), p.distribution[0].element_count))
+            .collect();
+        assert_eq!(
+            props,
+            [
+                ("data-items", Some(2)),
+                ("style", None),
+                ("style.margin", None)
+            ]
+        );
+    }
+}
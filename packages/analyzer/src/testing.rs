@@ -0,0 +1,40 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Temporary project on disk with a `package.json` and `tsconfig.json`, removed on drop
+pub struct TestProject {
+    root: PathBuf,
+}
+
+impl TestProject {
+    pub fn new(files: &[(&str, &str)]) -> Self {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let root = std::env::temp_dir().join(format!(
+            "cuin-test-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        let defaults = [
+            ("package.json", r#"{"name":"app","version":"1.0.0"}"#),
+            ("tsconfig.json", "{}"),
+        ];
+        for (path, content) in defaults.iter().chain(files) {
+            let path = root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+
+        Self { root }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+}
+
+impl Drop for TestProject {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.root);
+    }
+}
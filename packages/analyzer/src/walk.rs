@@ -1,7 +1,16 @@
 use ignore::WalkBuilder;
-use std::path::{Path, PathBuf};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::path::{Component, Path, PathBuf};
 use std::sync::mpsc;
 
+use crate::check::matches_pattern;
+use crate::parser;
+
+/// Extensions of compiled modules, analyzed in dependency packages in addition to the target
+/// extensions
+const COMPILED_EXTENSIONS: [&str; 2] = ["mjs", "cjs"];
+
 pub fn collect_files(path: &Path, extensions: &[String]) -> Vec<PathBuf> {
     let extensions_str: Vec<&str> = extensions.iter().map(|s| s.as_str()).collect();
     collect_files_with_extensions(path, &extensions_str)
@@ -49,6 +58,190 @@ fn collect_files_with_extensions(path: &Path, extensions: &[&str]) -> Vec<PathBu
     rx.iter().collect()
 }
 
+/// Collect the files of dependency packages whose name matches one of `patterns`
+///
+/// Packages are looked up in the `node_modules` directories of `root` and its ancestors, the
+/// closest one winning as in Node resolution. Only the modules reachable from the package
+/// entry points are collected; type declarations are skipped.
+pub fn collect_package_files(
+    root: &Path,
+    patterns: &[String],
+    extensions: &[String],
+) -> Vec<PathBuf> {
+    let mut extensions: Vec<&str> = extensions.iter().map(|s| s.as_str()).collect();
+    extensions.extend(COMPILED_EXTENSIONS);
+
+    let mut seen = HashSet::new();
+    let mut files = Vec::new();
+    for node_modules in root.ancestors().map(|dir| dir.join("node_modules")) {
+        for (name, package_dir) in list_packages(&node_modules) {
+            if !patterns.iter().any(|p| matches_pattern(p, &name)) || !seen.insert(name) {
+                continue;
+            }
+            files.extend(collect_package_dir(&package_dir, &extensions));
+        }
+    }
+    files
+}
+
+/// Package names and directories in a `node_modules` directory, including scoped packages
+fn list_packages(node_modules: &Path) -> Vec<(String, PathBuf)> {
+    let Ok(entries) = std::fs::read_dir(node_modules) else {
+        return Vec::new();
+    };
+
+    let mut packages = Vec::new();
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with('.') {
+            continue;
+        }
+        if name.starts_with('@') {
+            let Ok(scoped) = std::fs::read_dir(entry.path()) else {
+                continue;
+            };
+            packages.extend(scoped.flatten().map(|entry| {
+                let package = entry.file_name().to_string_lossy().to_string();
+                (format!("{name}/{package}"), entry.path())
+            }));
+        } else {
+            packages.push((name, entry.path()));
+        }
+    }
+    packages
+}
+
+/// Collect the modules a package publishes
+///
+/// Starts from the entry points in package.json and follows relative imports, so source
+/// files, stories, tests and the other build formats shipped next to the entries are left out.
+fn collect_package_dir(package_dir: &Path, extensions: &[&str]) -> Vec<PathBuf> {
+    let mut pending: Vec<PathBuf> = package_entries(package_dir)
+        .iter()
+        .filter_map(|entry| resolve_module(&package_dir.join(entry), extensions))
+        .collect();
+    let mut seen = HashSet::new();
+    let mut files = Vec::new();
+
+    while let Some(file) = pending.pop() {
+        let file = normalize(&file);
+        if !seen.insert(file.clone())
+            || !is_target_file(&file, extensions)
+            || is_declaration_file(&file)
+        {
+            continue;
+        }
+        let Ok(source) = std::fs::read_to_string(&file) else {
+            continue;
+        };
+        let dir = file.parent().unwrap_or(package_dir);
+        pending.extend(
+            parser::relative_imports(&source, &file)
+                .iter()
+                .filter_map(|specifier| resolve_module(&dir.join(specifier), extensions)),
+        );
+        files.push(file);
+    }
+
+    files.sort();
+    files
+}
+
+/// Conditions of the `exports` field in order of preference, choosing one build per entry
+const EXPORT_CONDITIONS: [&str; 5] = ["import", "module", "default", "require", "node"];
+
+/// Entry points of a package relative to its directory
+///
+/// Every subpath of `exports` contributes one target; `module`, `main` and `index.js` are the
+/// fallbacks when there is no `exports` field.
+fn package_entries(package_dir: &Path) -> Vec<String> {
+    let manifest = std::fs::read_to_string(package_dir.join("package.json"))
+        .ok()
+        .and_then(|json| serde_json::from_str::<Value>(&json).ok())
+        .unwrap_or(Value::Null);
+
+    let mut entries = Vec::new();
+    if let Some(exports) = manifest.get("exports") {
+        export_targets(exports, &mut entries);
+    }
+    if entries.is_empty() {
+        let field = ["module", "main"]
+            .iter()
+            .find_map(|field| manifest.get(*field).and_then(Value::as_str));
+        entries.push(field.unwrap_or("index.js").to_string());
+    }
+    entries
+}
+
+fn export_targets(exports: &Value, targets: &mut Vec<String>) {
+    match exports {
+        // Subpath patterns such as `./*` cannot be enumerated
+        Value::String(target) if !target.contains('*') => targets.push(target.clone()),
+        Value::Array(alternatives) => {
+            if let Some(first) = alternatives.first() {
+                export_targets(first, targets);
+            }
+        }
+        Value::Object(map) if map.keys().any(|key| key.starts_with('.')) => {
+            for value in map.values() {
+                export_targets(value, targets);
+            }
+        }
+        Value::Object(conditions) => {
+            if let Some(value) = EXPORT_CONDITIONS
+                .iter()
+                .find_map(|condition| conditions.get(*condition))
+            {
+                export_targets(value, targets);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Resolve a module path as Node and bundlers do: the file itself, the file with one of the
+/// extensions, or an index file in the directory
+fn resolve_module(path: &Path, extensions: &[&str]) -> Option<PathBuf> {
+    if path.is_file() {
+        return Some(path.to_path_buf());
+    }
+    let with_extension = extensions.iter().map(|ext| {
+        let mut file = path.as_os_str().to_owned();
+        file.push(format!(".{ext}"));
+        PathBuf::from(file)
+    });
+    let index = extensions
+        .iter()
+        .map(|ext| path.join(format!("index.{ext}")));
+    with_extension.chain(index).find(|file| file.is_file())
+}
+
+/// Remove `.` and `..` components without touching the file system
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// `.d.ts`, `.d.mts` and `.d.cts` files hold no usages
+fn is_declaration_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| {
+            [".d.ts", ".d.mts", ".d.cts"]
+                .iter()
+                .any(|suffix| name.ends_with(suffix))
+        })
+}
+
 #[inline]
 fn is_target_file(path: &Path, extensions: &[&str]) -> bool {
     path.extension()
@@ -0,0 +1,86 @@
+import { existsSync } from "node:fs";
+import { readFile, writeFile } from "node:fs/promises";
+import consola from "consola";
+import { check as checkUsage } from "cuin-analyzer";
+import { define } from "gunshi";
+
+type CheckOutcome = {
+  passed: boolean;
+  violations: {
+    kind: string;
+    name: string;
+    baseline_count: number;
+    current_count: number;
+  }[];
+  current: unknown;
+};
+
+export const check = define({
+  name: "check",
+  description:
+    "Fail when usage restricted by the policy grows beyond the baseline",
+  args: {
+    path: {
+      type: "string",
+      short: "p",
+      description: "Path to process",
+      default: process.cwd(),
+    },
+    config: {
+      type: "string",
+      short: "c",
+      description: "Path to the check policy JSON",
+      default: "cuin.config.json",
+    },
+    baseline: {
+      type: "string",
+      short: "b",
+      description: "Path to the baseline JSON",
+      default: "cuin-baseline.json",
+    },
+    "update-baseline": {
+      type: "boolean",
+      description: "Write the current usage to the baseline instead of checking",
+      default: false,
+    },
+  },
+  run: async (ctx) => {
+    const { path, config, baseline } = ctx.values;
+    const updateBaseline = ctx.values["update-baseline"];
+
+    const policy = await readFile(config, "utf-8");
+    const baselineJson = existsSync(baseline)
+      ? await readFile(baseline, "utf-8")
+      : undefined;
+    if (!(baselineJson || updateBaseline)) {
+      consola.warn(
+        `Baseline ${baseline} not found. Run with --update-baseline to create it.`
+      );
+    }
+
+    const outcome: CheckOutcome = JSON.parse(
+      checkUsage(path, policy, baselineJson)
+    );
+
+    if (updateBaseline) {
+      await writeFile(
+        baseline,
+        `${JSON.stringify(outcome.current, null, 2)}\n`
+      );
+      consola.success(`Baseline written to ${baseline}`);
+      return;
+    }
+
+    if (outcome.passed) {
+      consola.success("No usage grew beyond the baseline");
+      return;
+    }
+
+    for (const violation of outcome.violations) {
+      consola.error(
+        `${violation.kind}: ${violation.name} (${violation.baseline_count} -> ${violation.current_count})`
+      );
+    }
+    process.exitCode = 1;
+  },
+});
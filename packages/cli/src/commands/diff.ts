@@ -0,0 +1,50 @@
+import { readFile, writeFile } from "node:fs/promises";
+import consola from "consola";
+import { diff as diffReports } from "cuin-analyzer";
+import { define } from "gunshi";
+
+export const diff = define({
+  name: "diff",
+  description: "Compare two analysis reports",
+  args: {
+    base: {
+      type: "string",
+      short: "b",
+      description: "Path to the base report JSON",
+      required: true,
+    },
+    head: {
+      type: "string",
+      short: "H",
+      description: "Path to the head report JSON",
+      required: true,
+    },
+    format: {
+      type: "string",
+      short: "f",
+      description: "Output format (json or markdown)",
+      default: "markdown",
+    },
+    output: {
+      type: "string",
+      short: "o",
+      description: "Write the result to a file instead of stdout",
+    },
+  },
+  run: async (ctx) => {
+    const { base, head, format, output } = ctx.values;
+    const [baseReport, headReport] = await Promise.all([
+      readFile(base, "utf-8"),
+      readFile(head, "utf-8"),
+    ]);
+
+    const result = diffReports(baseReport, headReport, format);
+
+    if (output) {
+      await writeFile(output, result);
+      consola.success(`Diff written to ${output}`);
+    } else {
+      process.stdout.write(`${result}\n`);
+    }
+  },
+});
@@ -0,0 +1,59 @@
+import consola from "consola";
+import { query as runQuery } from "cuin-analyzer";
+import { define } from "gunshi";
+
+type Instance = {
+  file_path: string;
+  span: { start_line: number; start_col: number };
+};
+
+type Component = {
+  name: string;
+  instances: Instance[];
+};
+
+export const query = define({
+  name: "query",
+  description:
+    'Find usages matching a query, e.g. Button[variant="primary"][!size] in src/pages/**',
+  args: {
+    query: {
+      type: "string",
+      short: "q",
+      description: "Query to evaluate",
+      required: true,
+    },
+    path: {
+      type: "string",
+      short: "p",
+      description: "Path to process",
+      default: process.cwd(),
+    },
+    json: {
+      type: "boolean",
+      description: "Print the matching components as JSON",
+      default: false,
+    },
+  },
+  run: (ctx) => {
+    const { path, json } = ctx.values;
+    const result = runQuery(path, ctx.values.query);
+
+    if (json) {
+      process.stdout.write(`${result}\n`);
+      return;
+    }
+
+    const components: Component[] = JSON.parse(result);
+    for (const component of components) {
+      for (const instance of component.instances) {
+        const { start_line, start_col } = instance.span;
+        process.stdout.write(
+          `${instance.file_path}:${start_line}:${start_col}\t${component.name}\n`
+        );
+      }
+    }
+    const total = components.reduce((sum, c) => sum + c.instances.length, 0);
+    consola.info(`${total} matching usages`);
+  },
+});
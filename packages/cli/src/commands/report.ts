@@ -0,0 +1,81 @@
+import { readFile, writeFile } from "node:fs/promises";
+import consola from "consola";
+import * as analyzer from "cuin-analyzer";
+import { define } from "gunshi";
+
+// Only exported by analyzer builds with the `sqlite` feature
+const { exportSqlite } = analyzer as typeof analyzer & {
+  exportSqlite?: (inputPath: string, outputPath: string) => void;
+};
+
+export const report = define({
+  name: "report",
+  description: "Write the analysis report in the given format",
+  args: {
+    path: {
+      type: "string",
+      short: "p",
+      description: "Path to process",
+      default: process.cwd(),
+    },
+    format: {
+      type: "string",
+      short: "f",
+      description:
+        "Output format (json, sarif, html, markdown, csv-components, csv-instances, csv-props or sqlite)",
+      default: "json",
+    },
+    rules: {
+      type: "string",
+      short: "r",
+      description: "Path to a JSON file with usage rules",
+    },
+    output: {
+      type: "string",
+      short: "o",
+      description: "Write the report to a file instead of stdout",
+    },
+    packages: {
+      type: "string",
+      description:
+        "Comma-separated dependency packages in node_modules to analyze as well (e.g. @acme/*)",
+    },
+  },
+  run: async (ctx) => {
+    const { path, format, rules, output, packages } = ctx.values;
+
+    if (format === "sqlite") {
+      if (!exportSqlite) {
+        consola.error(
+          "The sqlite format requires the analyzer built with the sqlite feature (pnpm build:sqlite)",
+        );
+        process.exitCode = 1;
+        return;
+      }
+      if (!output) {
+        consola.error("The sqlite format requires --output");
+        process.exitCode = 1;
+        return;
+      }
+      exportSqlite(path, output);
+      consola.success(`Database written to ${output}`);
+      return;
+    }
+
+    const rulesJson = rules ? await readFile(rules, "utf-8") : undefined;
+
+    const packageList = packages
+      ?.split(",")
+      .map((name) => name.trim())
+      .filter((name) => name !== "");
+
+    const result = analyzer.analyze(path, rulesJson, format, packageList);
+
+    if (output) {
+      await writeFile(output, result);
+      consola.success(`Report written to ${output}`);
+    } else {
+      process.stdout.write(`${result}\n`);
+    }
+  },
+});
@@ -0,0 +1,44 @@
+import consola from "consola";
+import { aggregate, analyzeStream } from "cuin-analyzer";
+import { define } from "gunshi";
+
+export const stream = define({
+  name: "stream",
+  description: "Write usages as NDJSON while files are analyzed",
+  args: {
+    path: {
+      type: "string",
+      short: "p",
+      description: "Path to process",
+      default: process.cwd(),
+    },
+    output: {
+      type: "string",
+      short: "o",
+      description: "Path of the NDJSON file to write",
+      default: "cuin-usages.ndjson",
+    },
+    "per-file": {
+      type: "boolean",
+      description: "Write one line per file instead of one line per usage",
+      default: false,
+    },
+    aggregate: {
+      type: "string",
+      short: "a",
+      description: "Aggregate an existing NDJSON file instead of analyzing",
+    },
+  },
+  run: (ctx) => {
+    const { path, output } = ctx.values;
+
+    if (ctx.values.aggregate) {
+      process.stdout.write(`${aggregate(ctx.values.aggregate)}\n`);
+      return;
+    }
+
+    const granularity = ctx.values["per-file"] ? "file" : "usage";
+    analyzeStream(path, output, granularity);
+    consola.success(`Usages written to ${output}`);
+  },
+});
@@ -1,8 +1,18 @@
 import { type Command, cli } from "gunshi";
+import { check } from "./commands/check";
 import { dev } from "./commands/dev";
+import { diff } from "./commands/diff";
+import { query } from "./commands/query";
+import { report } from "./commands/report";
+import { stream } from "./commands/stream";
 
 const subCommands = new Map<string, Command>();
 subCommands.set("dev", dev);
+subCommands.set("check", check);
+subCommands.set("diff", diff);
+subCommands.set("query", query);
+subCommands.set("report", report);
+subCommands.set("stream", stream);
 
 await cli(process.argv.slice(2), dev, {
   name: "cuin",
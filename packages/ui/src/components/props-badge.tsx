@@ -18,6 +18,12 @@ type PropType =
   | "call"
   | "arrow"
   | "conditional"
+  | "template"
+  | "logical"
+  | "unary"
+  | "binary"
+  | "object"
+  | "array"
   | "expression"
   | "jsx"
   | "fragment"
@@ -66,6 +72,20 @@ export const PropsBadge: Component<PropsBadgeProps> = (props) => {
             <Match when={props.propType === "conditional"}>
               <MemberIcon class="h-4 w-4" />
             </Match>
+            <Match when={props.propType === "template"}>
+              <StringIcon class="h-4 w-4" />
+            </Match>
+            <Match
+              when={
+                props.propType === "logical" ||
+                props.propType === "unary" ||
+                props.propType === "binary" ||
+                props.propType === "object" ||
+                props.propType === "array"
+              }
+            >
+              <MemberIcon class="h-4 w-4" />
+            </Match>
             <Match when={props.propType === "expression"}>
               <MemberIcon class="h-4 w-4" />
             </Match>
@@ -16,6 +16,7 @@ import {
 } from "valibot";
 
 const Meta = object({
+  schemaVersion: optional(number()),
   basePath: string(),
 });
 
@@ -39,6 +40,8 @@ const Props = object({
   raw: string(),
   propType: string(),
   value: optional(string()),
+  shape: optional(string()),
+  elementCount: optional(number()),
 });
 export type Props = InferOutput<typeof Props>;
 